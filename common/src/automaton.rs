@@ -0,0 +1,565 @@
+//! A cellular automaton engine that steps any of the grids until they settle.
+//!
+//! An [`Automaton`] applies a rule to every cell of a grid to get the next generation, either a
+//! closure that sees the cell and its neighbours, or a [`CountRule`] that only looks at how many
+//! neighbours are present. It stops at a fixed point, at a cycle if asked to look for them, or
+//! after a number of generations.
+//!
+//! ```
+//! use aoc_common::automaton::{Automaton, CountRule, Outcome};
+//! use aoc_common::BitGrid;
+//!
+//! // A blinker in Conway's game of life flips between a line and a column
+//! let mut grid = BitGrid::read::<char>(".....\n.....\n.###.\n.....\n.....".lines())?;
+//! let life = Automaton::counting(CountRule::new(&[3], &[2, 3]), true)
+//!     .diagonal(true)
+//!     .detect_cycles();
+//! assert_eq!(life.run(&mut grid, |_| {}), Outcome::Cycle { start: 0, length: 2 });
+//! # Ok::<(), aoc_common::ParseError>(())
+//! ```
+
+use std::hash::{BuildHasher, Hash};
+
+use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
+
+use crate::coord::SignedCoordinate;
+use crate::grid::{BitGrid, CharConvertable, Grid, InfiniteGrid};
+
+/// A cell state that is either present or absent, so rules can count present neighbours.
+pub trait Occupancy {
+    /// Whether the cell counts as present.
+    fn is_present(&self) -> bool;
+
+    /// The state of an absent cell.
+    fn absent() -> Self;
+}
+
+impl Occupancy for bool {
+    fn is_present(&self) -> bool {
+        *self
+    }
+
+    fn absent() -> Self {
+        false
+    }
+}
+
+impl<T> Occupancy for Option<T> {
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+
+    fn absent() -> Self {
+        None
+    }
+}
+
+/// A rule that only depends on whether a cell is present and how many of its neighbours are, like
+/// `B3/S23` for Conway's game of life.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CountRule {
+    birth: u16,
+    survival: u16,
+}
+
+impl CountRule {
+    /// An absent cell becomes present when its number of present neighbours is in `birth`, a
+    /// present cell stays present when it is in `survival`.
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        let mask = |counts: &[usize]| counts.iter().fold(0, |mask, count| mask | 1 << count);
+        Self {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    /// Whether an absent cell with `count` present neighbours becomes present.
+    pub fn births(&self, count: usize) -> bool {
+        count < 16 && self.birth >> count & 1 == 1
+    }
+
+    /// Whether a present cell with `count` present neighbours stays present.
+    pub fn survives(&self, count: usize) -> bool {
+        count < 16 && self.survival >> count & 1 == 1
+    }
+}
+
+/// A grid an [`Automaton`] can step.
+pub trait Cells: Clone {
+    /// The state of a single cell.
+    type Cell: Clone + PartialEq;
+
+    /// The state of the cell at `coord`, cells outside of a bounded grid are absent.
+    fn cell(&self, coord: &SignedCoordinate) -> Self::Cell;
+
+    /// Sets the state of the cell at `coord`.
+    fn set_cell(&mut self, coord: SignedCoordinate, cell: Self::Cell);
+
+    /// The cells that may change in the next generation, in the order they are updated.
+    fn candidates(&self, include_diagonal: bool) -> Vec<SignedCoordinate>;
+
+    /// The cells that aren't absent, in any order.
+    fn present(&self) -> Vec<(SignedCoordinate, Self::Cell)>;
+
+    /// Applies `rule` to all cells at once, for grids that can do better than going cell by cell.
+    /// Returns the number of cells that changed, or `None` to let the automaton go cell by cell.
+    fn step_counts(&mut self, rule: &CountRule, include_diagonal: bool) -> Option<usize> {
+        let _ = (rule, include_diagonal);
+        None
+    }
+}
+
+/// Only the present cells and their neighbours are candidates, so rules must leave absent cells
+/// without present neighbours absent. Like the other grids, the candidates are in reading order.
+impl<
+    Data: Clone + CharConvertable + PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool,
+> Cells for InfiniteGrid<SignedCoordinate, Data, CACHED_EXTENTS, INCLUDE_EMPTY>
+{
+    type Cell = Option<Data>;
+
+    fn cell(&self, coord: &SignedCoordinate) -> Self::Cell {
+        self.get(coord).cloned()
+    }
+
+    fn set_cell(&mut self, coord: SignedCoordinate, cell: Self::Cell) {
+        self.set(coord, cell);
+    }
+
+    fn candidates(&self, include_diagonal: bool) -> Vec<SignedCoordinate> {
+        let mut candidates = FxHashSet::default();
+        for (coord, _) in self.entries() {
+            candidates.insert(coord);
+            for_each_neighbour(coord, include_diagonal, |n| {
+                candidates.insert(n);
+            });
+        }
+        let mut candidates: Vec<_> = candidates.into_iter().collect();
+        candidates.sort_unstable_by_key(|coord| (coord.y, coord.x));
+        candidates
+    }
+
+    fn present(&self) -> Vec<(SignedCoordinate, Self::Cell)> {
+        self.entries()
+            .map(|(coord, data)| (coord, Some(data)))
+            .collect()
+    }
+}
+
+impl<Data: Clone + PartialEq> Cells for Grid<Data> {
+    type Cell = Option<Data>;
+
+    fn cell(&self, coord: &SignedCoordinate) -> Self::Cell {
+        self.get(coord).cloned()
+    }
+
+    fn set_cell(&mut self, coord: SignedCoordinate, cell: Self::Cell) {
+        self.set(coord, cell);
+    }
+
+    fn candidates(&self, _include_diagonal: bool) -> Vec<SignedCoordinate> {
+        self.coords().collect()
+    }
+
+    fn present(&self) -> Vec<(SignedCoordinate, Self::Cell)> {
+        self.entries()
+            .map(|(coord, data)| (coord, Some(data.clone())))
+            .collect()
+    }
+}
+
+impl Cells for BitGrid {
+    type Cell = bool;
+
+    fn cell(&self, coord: &SignedCoordinate) -> Self::Cell {
+        self.get(coord)
+    }
+
+    fn set_cell(&mut self, coord: SignedCoordinate, cell: Self::Cell) {
+        self.set(coord, cell);
+    }
+
+    fn candidates(&self, _include_diagonal: bool) -> Vec<SignedCoordinate> {
+        (0..self.height())
+            .flat_map(|y| (0..self.width()).map(move |x| SignedCoordinate::from((x, y))))
+            .collect()
+    }
+
+    fn present(&self) -> Vec<(SignedCoordinate, Self::Cell)> {
+        self.iter().map(|coord| (coord, true)).collect()
+    }
+
+    /// Counts the neighbours of 64 cells at a time.
+    fn step_counts(&mut self, rule: &CountRule, include_diagonal: bool) -> Option<usize> {
+        let counts = if include_diagonal {
+            self.neighbour_counts::<true>()
+        } else {
+            self.neighbour_counts::<false>()
+        };
+        let mut born = BitGrid::new(self.width(), self.height());
+        let mut survive = born.clone();
+        for count in 0..=8 {
+            if rule.births(count) {
+                born |= &counts.equal(count as u8);
+            }
+            if rule.survives(count) {
+                survive |= &counts.equal(count as u8);
+            }
+        }
+        let next = &(&*self & &survive) | &(&!&*self & &born);
+        let changed = (&*self ^ &next).count();
+        *self = next;
+        Some(changed)
+    }
+}
+
+/// Calls `func` with each neighbour of `coord`, in the order of `SignedCoordinate::neighbours`.
+fn for_each_neighbour(
+    coord: SignedCoordinate,
+    include_diagonal: bool,
+    func: impl FnMut(SignedCoordinate),
+) {
+    if include_diagonal {
+        coord.neighbours::<true>().for_each(func);
+    } else {
+        coord.neighbours::<false>().for_each(func);
+    }
+}
+
+/// How cells are updated within a generation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Update {
+    /// Every cell sees the previous generation.
+    Synchronous,
+    /// Cells are updated one by one in the order of [`Cells::candidates`], so later cells see the
+    /// new states of earlier ones.
+    InPlace,
+}
+
+/// Why the automaton stopped.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// Generation `generation` is the first one that the rule leaves unchanged.
+    FixedPoint {
+        /// The number of steps that changed something.
+        generation: usize,
+    },
+    /// Generation `start + length` is the same as generation `start`.
+    Cycle {
+        /// The first generation of the cycle.
+        start: usize,
+        /// The number of generations in the cycle.
+        length: usize,
+    },
+    /// The limit on the number of generations was reached.
+    Limit,
+}
+
+/// The state after a step, handed to the callback of [`Automaton::run`].
+#[derive(Debug)]
+pub struct Generation<'a, G> {
+    /// The number of steps taken so far, starting at 1.
+    pub number: usize,
+    /// The number of cells that changed in this step.
+    pub changed: usize,
+    /// The grid after the step.
+    pub grid: &'a G,
+}
+
+/// A rule that computes the next state of a cell from its state and the states of its neighbours.
+type RuleFn<'a, Cell> = dyn Fn(&Cell, &[Cell]) -> Cell + 'a;
+
+enum Rule<'a, Cell> {
+    Closure(Box<RuleFn<'a, Cell>>),
+    /// Keeps the functions of `Occupancy`, which only `Automaton::counting` requires.
+    Counts {
+        rule: CountRule,
+        born: Cell,
+        is_present: fn(&Cell) -> bool,
+        absent: fn() -> Cell,
+    },
+}
+
+/// Steps a grid of type `G` with a rule until it settles.
+pub struct Automaton<'a, G: Cells> {
+    rule: Rule<'a, G::Cell>,
+    include_diagonal: bool,
+    update: Update,
+    detect_cycles: bool,
+    limit: Option<usize>,
+}
+
+impl<'a, G: Cells> Automaton<'a, G> {
+    fn with_rule(rule: Rule<'a, G::Cell>) -> Self {
+        Self {
+            rule,
+            include_diagonal: false,
+            update: Update::Synchronous,
+            detect_cycles: false,
+            limit: None,
+        }
+    }
+
+    /// An automaton where `rule` gives the next state of a cell from its current state and the
+    /// states of its neighbours.
+    pub fn new(rule: impl Fn(&G::Cell, &[G::Cell]) -> G::Cell + 'a) -> Self {
+        Self::with_rule(Rule::Closure(Box::new(rule)))
+    }
+
+    /// An automaton following `rule`, where cells that are born get the state `born`.
+    pub fn counting(rule: CountRule, born: G::Cell) -> Self
+    where
+        G::Cell: Occupancy,
+    {
+        Self::with_rule(Rule::Counts {
+            rule,
+            born,
+            is_present: G::Cell::is_present,
+            absent: G::Cell::absent,
+        })
+    }
+
+    /// Whether the diagonal neighbours count as neighbours as well, off by default.
+    pub fn diagonal(mut self, include_diagonal: bool) -> Self {
+        self.include_diagonal = include_diagonal;
+        self
+    }
+
+    /// Updates the cells in place instead of synchronously.
+    pub fn in_place(mut self) -> Self {
+        self.update = Update::InPlace;
+        self
+    }
+
+    /// Stops at the first generation that repeats an earlier one. This keeps every generation
+    /// around, so it is off by default.
+    pub fn detect_cycles(mut self) -> Self {
+        self.detect_cycles = true;
+        self
+    }
+
+    /// Stops after at most `generations` steps.
+    pub fn limit(mut self, generations: usize) -> Self {
+        self.limit = Some(generations);
+        self
+    }
+
+    fn next(&self, grid: &G, coord: SignedCoordinate, buffer: &mut Vec<G::Cell>) -> G::Cell {
+        let cell = grid.cell(&coord);
+        buffer.clear();
+        for_each_neighbour(coord, self.include_diagonal, |n| buffer.push(grid.cell(&n)));
+        match &self.rule {
+            Rule::Closure(rule) => rule(&cell, buffer),
+            Rule::Counts {
+                rule,
+                born,
+                is_present,
+                absent,
+            } => {
+                let count = buffer.iter().filter(|n| is_present(n)).count();
+                match (is_present(&cell), rule.survives(count), rule.births(count)) {
+                    (true, true, _) | (false, _, false) => cell,
+                    (true, false, _) => absent(),
+                    (false, _, true) => born.clone(),
+                }
+            }
+        }
+    }
+
+    /// Advances `grid` by one generation, returning the number of cells that changed.
+    pub fn step(&self, grid: &mut G) -> usize {
+        if let (Rule::Counts { rule, .. }, Update::Synchronous) = (&self.rule, self.update)
+            && let Some(changed) = grid.step_counts(rule, self.include_diagonal)
+        {
+            return changed;
+        }
+
+        let mut buffer = Vec::new();
+        let mut changed = 0;
+        match self.update {
+            Update::Synchronous => {
+                let updates: Vec<_> = grid
+                    .candidates(self.include_diagonal)
+                    .into_iter()
+                    .filter_map(|coord| {
+                        let next = self.next(grid, coord, &mut buffer);
+                        (next != grid.cell(&coord)).then_some((coord, next))
+                    })
+                    .collect();
+                changed = updates.len();
+                for (coord, next) in updates {
+                    grid.set_cell(coord, next);
+                }
+            }
+            Update::InPlace => {
+                for coord in grid.candidates(self.include_diagonal) {
+                    let next = self.next(grid, coord, &mut buffer);
+                    if next != grid.cell(&coord) {
+                        grid.set_cell(coord, next);
+                        changed += 1;
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    /// Steps `grid` until it reaches a fixed point, a cycle if cycles are detected, or the limit.
+    /// `on_generation` is called after every step, including the last one that changes nothing.
+    pub fn run(&self, grid: &mut G, mut on_generation: impl FnMut(&Generation<G>)) -> Outcome
+    where
+        G::Cell: Hash,
+    {
+        let mut seen: FxHashMap<u64, Vec<(usize, G)>> = FxHashMap::default();
+        if self.detect_cycles {
+            seen.entry(fingerprint(grid))
+                .or_default()
+                .push((0, grid.clone()));
+        }
+
+        let mut number = 0;
+        while self.limit.is_none_or(|limit| number < limit) {
+            let changed = self.step(grid);
+            number += 1;
+            on_generation(&Generation {
+                number,
+                changed,
+                grid,
+            });
+            if changed == 0 {
+                return Outcome::FixedPoint {
+                    generation: number - 1,
+                };
+            }
+
+            if self.detect_cycles {
+                let states = seen.entry(fingerprint(grid)).or_default();
+                if let Some((start, _)) = states.iter().find(|(_, state)| same_cells(state, grid)) {
+                    return Outcome::Cycle {
+                        start: *start,
+                        length: number - start,
+                    };
+                }
+                states.push((number, grid.clone()));
+            }
+        }
+        Outcome::Limit
+    }
+}
+
+/// A hash of the present cells that doesn't depend on the order they are listed in.
+fn fingerprint<G: Cells>(grid: &G) -> u64
+where
+    G::Cell: Hash,
+{
+    grid.present()
+        .iter()
+        .map(|cell| FxBuildHasher.hash_one(cell))
+        .fold(0, u64::wrapping_add)
+}
+
+fn same_cells<G: Cells>(a: &G, b: &G) -> bool {
+    let present = a.present();
+    present.len() == b.present().len()
+        && present
+            .into_iter()
+            .all(|(coord, cell)| b.cell(&coord) == cell)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Automaton, CountRule, Outcome};
+    use crate::{BitGrid, Grid, InfiniteGrid, SignedCoordinate};
+
+    const GLIDER: &str = ".#...\n..#..\n###..\n.....\n.....";
+
+    #[test]
+    fn grids_agree() {
+        let life = CountRule::new(&[3], &[2, 3]);
+        let mut bits = BitGrid::read::<char>(GLIDER.lines()).unwrap();
+        let mut dense = Grid::<char>::read(GLIDER.lines()).unwrap();
+        let mut infinite =
+            InfiniteGrid::<SignedCoordinate, char, false>::read(GLIDER.lines()).unwrap();
+        for _ in 0..4 {
+            Automaton::counting(life, true)
+                .diagonal(true)
+                .step(&mut bits);
+            Automaton::counting(life, Some('#'))
+                .diagonal(true)
+                .step(&mut dense);
+            Automaton::counting(life, Some('#'))
+                .diagonal(true)
+                .step(&mut infinite);
+        }
+
+        // After four generations the glider moved one cell down and to the right
+        let expected = BitGrid::read::<char>(".....\n..#..\n...#.\n.###.\n.....".lines()).unwrap();
+        assert_eq!(bits, expected);
+        let coords = |grid: &Grid<char>| grid.entries().map(|(c, _)| c).collect::<Vec<_>>();
+        assert_eq!(coords(&dense), expected.iter().collect::<Vec<_>>());
+        let mut infinite: Vec<_> = infinite.entries().map(|(c, _)| c).collect();
+        infinite.sort_by_key(|c| (c.y, c.x));
+        assert_eq!(infinite, expected.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn outcomes_and_callbacks() {
+        let mut grid = BitGrid::read::<char>(GLIDER.lines()).unwrap();
+        let life = || Automaton::counting(CountRule::new(&[3], &[2, 3]), true).diagonal(true);
+        assert_eq!(life().limit(3).run(&mut grid, |_| {}), Outcome::Limit);
+
+        // The glider ends up as a block in the corner of the bounded grid
+        let mut changes = Vec::new();
+        let outcome = life().run(&mut grid, |generation| changes.push(generation.changed));
+        let Outcome::FixedPoint { generation } = outcome else {
+            panic!("{outcome:?}");
+        };
+        assert_eq!(changes.len(), generation + 1);
+        assert_eq!(changes.last(), Some(&0));
+        assert_eq!(grid.count(), 4);
+
+        // A closure that toggles every cell cycles with length 2
+        let mut grid = Grid::<char>::read("#.\n.#".lines()).unwrap();
+        let toggle = Automaton::new(|cell: &Option<char>, _: &[Option<char>]| match cell {
+            Some(_) => None,
+            None => Some('#'),
+        });
+        assert_eq!(
+            toggle.detect_cycles().run(&mut grid, |_| {}),
+            Outcome::Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+    }
+
+    #[test]
+    fn in_place_sees_earlier_updates() {
+        // A cell becomes present if its western neighbour is, so in place the first cell spreads
+        // all the way
+        let rule = |cell: &bool, neighbours: &[bool]| *cell || neighbours[1];
+        let mut synchronous = BitGrid::read::<char>("#....".lines()).unwrap();
+        Automaton::new(rule).step(&mut synchronous);
+        assert_eq!(format!("{synchronous:?}"), "##...\n");
+
+        let mut in_place = BitGrid::read::<char>("#....".lines()).unwrap();
+        Automaton::new(rule).in_place().step(&mut in_place);
+        assert_eq!(format!("{in_place:?}"), "#####\n");
+
+        // A cell is removed if its western neighbour is present, so the result depends on the
+        // order. Sparse grids update in reading order like the dense ones.
+        let rule = |cell: &Option<char>, neighbours: &[Option<char>]| {
+            if neighbours[1].is_some() { None } else { *cell }
+        };
+        let mut dense = Grid::<char>::read("###".lines()).unwrap();
+        Automaton::new(rule).in_place().step(&mut dense);
+        assert_eq!(format!("{dense:?}"), "#.#\n");
+        let mut sparse =
+            InfiniteGrid::<SignedCoordinate, char, false>::read("###".lines()).unwrap();
+        Automaton::new(rule).in_place().step(&mut sparse);
+        let mut cells: Vec<_> = sparse.entries().map(|(c, _)| c.x).collect();
+        cells.sort_unstable();
+        assert_eq!(cells, [0, 2]);
+    }
+}
@@ -0,0 +1,234 @@
+//! Coordinates on a grid.
+
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::direction::Facing;
+
+/// A position that can key a grid. Coordinates are built from the `(x, y)` position of a
+/// character in the input, with `y` counting lines.
+pub trait Coordinate: Clone + Eq + PartialEq + Hash + From<(usize, usize)> {
+    /// The component-wise minimum of `self` and `other`.
+    fn min(&self, other: &Self) -> Self;
+
+    /// The component-wise maximum of `self` and `other`.
+    fn max(&self, other: &Self) -> Self;
+
+    /// Every coordinate of the rectangle from `self` to `other` in reading order, flagged with
+    /// whether it is the last one of its line.
+    fn range_to_debug(&self, other: &Self) -> impl Iterator<Item = (bool, Self)>;
+}
+
+/// A coordinate that can go below zero, for grids that grow in every direction. `y` grows
+/// towards the south.
+///
+/// ```
+/// use aoc_common::{Facing, SignedCoordinate};
+///
+/// let start = SignedCoordinate::ZERO;
+/// assert_eq!(start.forward(Facing::North, 2), SignedCoordinate::new(0, -2));
+/// assert_eq!(start.east(1) * 3 - start.south(1), SignedCoordinate::new(3, -1));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SignedCoordinate {
+    /// The column, growing towards the east.
+    pub x: i64,
+    /// The line, growing towards the south.
+    pub y: i64,
+}
+
+impl From<(usize, usize)> for SignedCoordinate {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self {
+            x: x as i64,
+            y: y as i64,
+        }
+    }
+}
+
+impl SignedCoordinate {
+    /// The origin, which is the first character of the input.
+    pub const ZERO: SignedCoordinate = SignedCoordinate { x: 0, y: 0 };
+
+    /// Creates the coordinate `(x, y)`.
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The coordinate `amount` steps to the north.
+    pub fn north(&self, amount: i64) -> SignedCoordinate {
+        SignedCoordinate {
+            x: self.x,
+            y: self.y - amount,
+        }
+    }
+
+    /// The coordinate `amount` steps to the east.
+    pub fn east(&self, amount: i64) -> SignedCoordinate {
+        SignedCoordinate {
+            x: self.x + amount,
+            y: self.y,
+        }
+    }
+
+    /// The coordinate `amount` steps to the south.
+    pub fn south(&self, amount: i64) -> SignedCoordinate {
+        SignedCoordinate {
+            x: self.x,
+            y: self.y + amount,
+        }
+    }
+
+    /// The coordinate `amount` steps to the west.
+    pub fn west(&self, amount: i64) -> SignedCoordinate {
+        SignedCoordinate {
+            x: self.x - amount,
+            y: self.y,
+        }
+    }
+
+    /// The coordinate `amount` steps towards `facing`.
+    pub fn forward(&self, facing: Facing, amount: i64) -> SignedCoordinate {
+        match facing {
+            Facing::North => self.north(amount),
+            Facing::East => self.east(amount),
+            Facing::South => self.south(amount),
+            Facing::West => self.west(amount),
+        }
+    }
+
+    /// The number of orthogonal steps from `self` to `other`.
+    ///
+    /// ```
+    /// use aoc_common::SignedCoordinate;
+    ///
+    /// assert_eq!(SignedCoordinate::new(1, -2).manhattan(&SignedCoordinate::new(-3, 1)), 7);
+    /// ```
+    pub fn manhattan(&self, other: &SignedCoordinate) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The four orthogonally adjacent coordinates, or all eight adjacent coordinates if
+    /// `INCLUDE_DIAGONAL` is set.
+    ///
+    /// ```
+    /// use aoc_common::SignedCoordinate;
+    ///
+    /// let origin = SignedCoordinate::ZERO;
+    /// assert_eq!(origin.neighbours::<false>().count(), 4);
+    /// assert_eq!(origin.neighbours::<true>().count(), 8);
+    /// assert!(origin.neighbours::<true>().all(|n| n != origin));
+    /// ```
+    pub gen fn neighbours<const INCLUDE_DIAGONAL: bool>(&self) -> SignedCoordinate {
+        if INCLUDE_DIAGONAL {
+            for y in -1..=1 {
+                for x in -1..=1 {
+                    if x != 0 || y != 0 {
+                        yield SignedCoordinate::new(self.x + x, self.y + y);
+                    }
+                }
+            }
+        } else {
+            yield self.north(1);
+            yield self.west(1);
+            yield self.east(1);
+            yield self.south(1);
+        }
+    }
+}
+
+impl Debug for SignedCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for SignedCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Coordinate for SignedCoordinate {
+    fn min(&self, other: &Self) -> Self {
+        SignedCoordinate {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+        }
+    }
+
+    fn max(&self, other: &Self) -> Self {
+        SignedCoordinate {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+        }
+    }
+
+    fn range_to_debug(&self, other: &Self) -> impl Iterator<Item = (bool, Self)> {
+        (self.y..=other.y).flat_map(move |y| {
+            (self.x..=other.x).map(move |x| {
+                if x == other.x {
+                    (true, SignedCoordinate { x, y })
+                } else {
+                    (false, SignedCoordinate { x, y })
+                }
+            })
+        })
+    }
+}
+
+impl Add for SignedCoordinate {
+    type Output = SignedCoordinate;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for SignedCoordinate {
+    type Output = SignedCoordinate;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Mul<i64> for SignedCoordinate {
+    type Output = SignedCoordinate;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl Mul<SignedCoordinate> for i64 {
+    type Output = SignedCoordinate;
+
+    fn mul(self, rhs: SignedCoordinate) -> Self::Output {
+        SignedCoordinate {
+            x: self * rhs.x,
+            y: self * rhs.y,
+        }
+    }
+}
+
+impl Div<i64> for SignedCoordinate {
+    type Output = SignedCoordinate;
+
+    fn div(self, rhs: i64) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! The four directions of a grid.
+
+/// One of the four directions of a grid, where north is towards the first line of the input.
+///
+/// ```
+/// use aoc_common::Facing;
+///
+/// assert_eq!(Facing::North.right(), Facing::East);
+/// assert_eq!(Facing::North.left().left(), Facing::North.flip());
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Ord, PartialOrd)]
+pub enum Facing {
+    /// Up, towards decreasing `y`.
+    North,
+    /// Right, towards increasing `x`.
+    East,
+    /// Down, towards increasing `y`.
+    South,
+    /// Left, towards decreasing `x`.
+    West,
+}
+
+impl Facing {
+    /// Turns a quarter counterclockwise.
+    pub fn left(self) -> Self {
+        match self {
+            Facing::North => Facing::West,
+            Facing::East => Facing::North,
+            Facing::South => Facing::East,
+            Facing::West => Facing::South,
+        }
+    }
+
+    /// Turns a quarter clockwise.
+    pub fn right(self) -> Self {
+        match self {
+            Facing::North => Facing::East,
+            Facing::East => Facing::South,
+            Facing::South => Facing::West,
+            Facing::West => Facing::North,
+        }
+    }
+
+    /// Turns around.
+    pub fn flip(self) -> Self {
+        match self {
+            Facing::North => Facing::South,
+            Facing::East => Facing::West,
+            Facing::South => Facing::North,
+            Facing::West => Facing::East,
+        }
+    }
+}
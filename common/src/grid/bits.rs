@@ -0,0 +1,458 @@
+//! A bit-packed grid of present and absent cells.
+
+use std::fmt::{Debug, Formatter};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::CharConvertable;
+use crate::coord::SignedCoordinate;
+use crate::parse::ParseError;
+
+const BITS: usize = u64::BITS as usize;
+
+/// A rectangular grid that stores one bit per cell, for puzzles where a cell is either occupied or
+/// not. Each line is packed into `u64` words, so counting neighbours, population counts and
+/// combining grids work on 64 cells at once.
+///
+/// Like [`Grid`](super::Grid) the top left cell is at `(0, 0)` and cells outside of the grid are
+/// absent.
+///
+/// ```
+/// use aoc_common::{BitGrid, SignedCoordinate};
+///
+/// let grid = BitGrid::read::<char>("##.\n.#.\n...".lines())?;
+/// assert_eq!(grid.count(), 3);
+/// assert!(grid.get(&SignedCoordinate::new(1, 1)));
+///
+/// // Cells with fewer than two occupied neighbours, including the diagonals
+/// let lonely = &grid & &grid.neighbour_counts::<true>().less_than(2);
+/// assert_eq!(format!("{lonely:?}"), "...\n...\n...\n");
+/// let crowded = grid.neighbour_counts::<true>().equal(3);
+/// assert_eq!(format!("{crowded:?}"), "...\n#..\n...\n");
+/// # Ok::<(), aoc_common::ParseError>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    /// The lines one after another, bit `x % 64` of word `x / 64` of a line is column `x`. Bits
+    /// past the width are always zero.
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid of `width` by `height` absent cells.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(BITS);
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    /// Reads a grid from the lines of the input, where a cell is present if `Data` reads a value
+    /// from its character. Surrounding whitespace is ignored and short lines are padded with
+    /// absent cells. Fails with the position of the first character that `Data` rejects.
+    pub fn read<Data: CharConvertable>(
+        lines: impl Iterator<Item = impl AsRef<str>>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = Vec::new();
+        for (y, line) in lines.enumerate() {
+            let line = line.as_ref();
+            let indent = line[..line.len() - line.trim_start().len()].chars().count();
+            let mut row = Vec::new();
+            for (x, c) in line.trim().chars().enumerate() {
+                let data = Data::from_char(c).map_err(|e| e.at(y + 1, indent + x + 1))?;
+                row.push(data.is_some());
+            }
+            rows.push(row);
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut grid = Self::new(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, present) in row.iter().enumerate() {
+                if *present {
+                    grid.words[y * grid.words_per_row + x / BITS] |= 1 << (x % BITS);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of lines.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn position(&self, coord: &SignedCoordinate) -> Option<(usize, u64)> {
+        let x = usize::try_from(coord.x).ok()?;
+        let y = usize::try_from(coord.y).ok()?;
+        (x < self.width && y < self.height)
+            .then_some((y * self.words_per_row + x / BITS, 1 << (x % BITS)))
+    }
+
+    /// Whether `coord` is inside the grid.
+    pub fn contains(&self, coord: &SignedCoordinate) -> bool {
+        self.position(coord).is_some()
+    }
+
+    /// Whether the cell at `coord` is present, cells outside of the grid are absent.
+    pub fn get(&self, coord: &SignedCoordinate) -> bool {
+        self.position(coord)
+            .is_some_and(|(word, bit)| self.words[word] & bit != 0)
+    }
+
+    /// Sets whether the cell at `coord` is present. Returns whether it was present before.
+    ///
+    /// Panics if `coord` is outside of the grid.
+    pub fn set(&mut self, coord: SignedCoordinate, present: bool) -> bool {
+        let (word, bit) = self
+            .position(&coord)
+            .unwrap_or_else(|| panic!("{coord} is outside of the grid"));
+        let previous = self.words[word] & bit != 0;
+        if present {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+        previous
+    }
+
+    /// The number of present cells.
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Whether no cell is present.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// The coordinates of the present cells in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = SignedCoordinate> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let y = i / self.words_per_row;
+            let x = (i % self.words_per_row) * BITS;
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(SignedCoordinate::from((x + bit, y)))
+            })
+        })
+    }
+
+    /// The mask of the bits of the last word of a line that are inside the grid.
+    fn last_word_mask(&self) -> u64 {
+        match self.width % BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        if self.words_per_row == 0 {
+            return;
+        }
+        let mask = self.last_word_mask();
+        for row in self.words.chunks_mut(self.words_per_row) {
+            *row.last_mut().unwrap() &= mask;
+        }
+    }
+
+    /// The line `y`, or no words if it is outside of the grid.
+    fn row(&self, y: Option<usize>) -> &[u64] {
+        match y {
+            Some(y) if y < self.height => {
+                &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+            }
+            _ => &[],
+        }
+    }
+
+    /// Counts the present neighbours of every cell, 64 cells at a time. Only the four orthogonal
+    /// neighbours are counted unless `INCLUDE_DIAGONAL` is set.
+    pub fn neighbour_counts<const INCLUDE_DIAGONAL: bool>(&self) -> NeighbourCounts {
+        let mut planes: [Self; 4] = std::array::from_fn(|_| Self::new(self.width, self.height));
+        let word = |row: &[u64], i: Option<usize>| i.and_then(|i| row.get(i)).copied().unwrap_or(0);
+        for y in 0..self.height {
+            let above = self.row(y.checked_sub(1));
+            let current = self.row(Some(y));
+            let below = self.row(Some(y + 1));
+            for i in 0..self.words_per_row {
+                // A neighbour to the west of a cell is a bit one lower, so shift the line up by
+                // one, carrying the top bit of the previous word, and the other way around for
+                // the east.
+                let west =
+                    |row: &[u64]| word(row, Some(i)) << 1 | word(row, i.checked_sub(1)) >> 63;
+                let east = |row: &[u64]| word(row, Some(i)) >> 1 | word(row, Some(i + 1)) << 63;
+
+                let neighbours = [
+                    word(above, Some(i)),
+                    west(current),
+                    east(current),
+                    word(below, Some(i)),
+                    west(above),
+                    east(above),
+                    west(below),
+                    east(below),
+                ];
+                let neighbours = if INCLUDE_DIAGONAL {
+                    &neighbours[..]
+                } else {
+                    &neighbours[..4]
+                };
+
+                // Add each neighbour into a bit-sliced 4 bit counter
+                let index = y * self.words_per_row + i;
+                for &(mut carry) in neighbours {
+                    for plane in &mut planes {
+                        let bit = plane.words[index];
+                        plane.words[index] = bit ^ carry;
+                        carry &= bit;
+                    }
+                }
+            }
+        }
+        for plane in &mut planes {
+            plane.clear_padding();
+        }
+        NeighbourCounts { planes }
+    }
+
+    fn zip_with(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Grids of different sizes: {}x{} and {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word = op(*word, *other);
+        }
+    }
+}
+
+/// The number of present neighbours of every cell of a [`BitGrid`], stored as four bit planes so
+/// the counts can be compared 64 cells at a time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NeighbourCounts {
+    planes: [BitGrid; 4],
+}
+
+impl NeighbourCounts {
+    /// The number of present neighbours of the cell at `coord`, or 0 outside of the grid.
+    pub fn get(&self, coord: &SignedCoordinate) -> u8 {
+        (0..4)
+            .filter(|&i| self.planes[i].get(coord))
+            .map(|i| 1 << i)
+            .sum()
+    }
+
+    /// The cells with fewer than `count` present neighbours.
+    pub fn less_than(&self, count: u8) -> BitGrid {
+        self.compare(count).0
+    }
+
+    /// The cells with exactly `count` present neighbours.
+    pub fn equal(&self, count: u8) -> BitGrid {
+        self.compare(count).1
+    }
+
+    /// The cells with at least `count` present neighbours.
+    pub fn at_least(&self, count: u8) -> BitGrid {
+        !&self.less_than(count)
+    }
+
+    /// Compares every count with `count`, from the most significant bit down, giving the cells
+    /// whose count is less and those whose count is equal.
+    fn compare(&self, count: u8) -> (BitGrid, BitGrid) {
+        let mut less = self.planes[0].clone();
+        let mut equal = self.planes[0].clone();
+        for (i, (less, equal)) in less.words.iter_mut().zip(&mut equal.words).enumerate() {
+            // Counts never exceed 8, so they are all less than anything that needs more planes
+            if count > 0b1111 {
+                (*less, *equal) = (u64::MAX, 0);
+                continue;
+            }
+            (*less, *equal) = (0, u64::MAX);
+            for (bit, plane) in self.planes.iter().enumerate().rev() {
+                let plane = plane.words[i];
+                if count >> bit & 1 == 1 {
+                    *less |= *equal & !plane;
+                    *equal &= plane;
+                } else {
+                    *equal &= !plane;
+                }
+            }
+        }
+        less.clear_padding();
+        equal.clear_padding();
+        (less, equal)
+    }
+}
+
+macro_rules! bit_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $symbol:tt) => {
+        impl $assign<&BitGrid> for BitGrid {
+            /// Panics if the grids have different sizes.
+            fn $assign_method(&mut self, other: &BitGrid) {
+                self.zip_with(other, |a, b| a $symbol b);
+            }
+        }
+
+        impl $op for &BitGrid {
+            type Output = BitGrid;
+
+            /// Panics if the grids have different sizes.
+            fn $method(self, other: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.zip_with(other, |a, b| a $symbol b);
+                result
+            }
+        }
+    };
+}
+
+bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        for word in &mut result.words {
+            *word = !*word;
+        }
+        result.clear_padding();
+        result
+    }
+}
+
+/// Draws present cells as `#` and absent cells as `.`.
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let present = self.get(&SignedCoordinate::from((x, y)));
+                write!(f, "{}", if present { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitGrid;
+    use crate::SignedCoordinate;
+    use crate::test_util::Rng;
+
+    fn random_grid(rng: &mut Rng, width: usize, height: usize) -> BitGrid {
+        let mut grid = BitGrid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                grid.set(SignedCoordinate::from((x, y)), rng.next().is_multiple_of(3));
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn counts_neighbours_like_the_naive_way() {
+        let mut rng = Rng::new(0x2025_1204);
+        // Widths around the word size catch mistakes in carrying bits between words
+        for (width, height) in [(1, 1), (5, 3), (63, 4), (64, 3), (65, 5), (130, 7)] {
+            let grid = random_grid(&mut rng, width, height);
+            let diagonal = grid.neighbour_counts::<true>();
+            let orthogonal = grid.neighbour_counts::<false>();
+            for y in 0..height {
+                for x in 0..width {
+                    let coord = SignedCoordinate::from((x, y));
+                    let naive = |diagonal: bool| {
+                        let neighbours: Vec<_> = if diagonal {
+                            coord.neighbours::<true>().collect()
+                        } else {
+                            coord.neighbours::<false>().collect()
+                        };
+                        neighbours.iter().filter(|n| grid.get(n)).count() as u8
+                    };
+                    assert_eq!(
+                        diagonal.get(&coord),
+                        naive(true),
+                        "{coord} of {width}x{height}"
+                    );
+                    assert_eq!(
+                        orthogonal.get(&coord),
+                        naive(false),
+                        "{coord} of {width}x{height}"
+                    );
+                    for count in 0..=9 {
+                        let expected = naive(true);
+                        assert_eq!(diagonal.less_than(count).get(&coord), expected < count);
+                        assert_eq!(diagonal.equal(count).get(&coord), expected == count);
+                        assert_eq!(diagonal.at_least(count).get(&coord), expected >= count);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn boolean_ops_and_counts() {
+        let mut rng = Rng::new(7);
+        let a = random_grid(&mut rng, 70, 3);
+        let b = random_grid(&mut rng, 70, 3);
+        let all = |grid: &BitGrid| -> Vec<_> { grid.iter().collect() };
+
+        assert_eq!(all(&a).len(), a.count());
+        assert_eq!((&a | &b).count() + (&a & &b).count(), a.count() + b.count());
+        assert_eq!((&a ^ &b).count(), (&a | &b).count() - (&a & &b).count());
+        assert_eq!((!&a).count(), 70 * 3 - a.count());
+        assert!((&a & &!&a).is_empty());
+
+        let mut c = a.clone();
+        c ^= &a;
+        assert!(c.is_empty());
+        c |= &b;
+        c &= &a;
+        assert_eq!(c, &a & &b);
+
+        assert!(!a.get(&SignedCoordinate::new(70, 0)));
+        assert!(!a.get(&SignedCoordinate::new(-1, 0)));
+    }
+
+    #[test]
+    fn reads_and_draws() {
+        let grid = BitGrid::read::<char>("#.#\n .#\n".lines()).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(format!("{grid:?}"), "#.#\n.#.\n");
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            [
+                SignedCoordinate::new(0, 0),
+                SignedCoordinate::new(2, 0),
+                SignedCoordinate::new(1, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! A dense grid for inputs with a fixed size.
+
+use std::fmt::{Debug, Formatter};
+
+use super::{CharConvertable, InfiniteGrid};
+use crate::coord::SignedCoordinate;
+use crate::parse::ParseError;
+
+/// A rectangular grid that stores every cell, so looking up a cell is a bounds check and an index
+/// instead of a hash. Cells outside of the grid read as empty.
+///
+/// The top left cell is at `origin()`, which is `(0, 0)` for a grid read from the input. Grids
+/// converted from an [`InfiniteGrid`] keep their coordinates, so the origin can be negative.
+///
+/// ```
+/// use aoc_common::{Grid, InfiniteGrid, SignedCoordinate};
+///
+/// let grid = Grid::<char>::read("#..\n.#.".lines())?;
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid.get(&SignedCoordinate::new(1, 1)), Some(&'#'));
+/// assert_eq!(grid.get(&SignedCoordinate::new(-1, 0)), None);
+///
+/// let sparse = InfiniteGrid::<SignedCoordinate, char, false>::from(grid.clone());
+/// assert_eq!(Grid::from(&sparse).width(), 2);
+/// # Ok::<(), aoc_common::ParseError>(())
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Grid<Data> {
+    origin: SignedCoordinate,
+    width: usize,
+    height: usize,
+    cells: Vec<Option<Data>>,
+}
+
+impl<Data: Clone> Grid<Data> {
+    /// An empty grid of `width` by `height` cells with its top left cell at `(0, 0)`.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_origin(SignedCoordinate::ZERO, width, height)
+    }
+
+    /// An empty grid of `width` by `height` cells with its top left cell at `origin`.
+    pub fn with_origin(origin: SignedCoordinate, width: usize, height: usize) -> Self {
+        Self {
+            origin,
+            width,
+            height,
+            cells: vec![None; width * height],
+        }
+    }
+}
+
+impl<Data> Grid<Data> {
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of lines.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The coordinate of the top left cell.
+    pub fn origin(&self) -> SignedCoordinate {
+        self.origin
+    }
+
+    /// The top left and bottom right coordinates of the grid, like [`InfiniteGrid::extents`].
+    ///
+    /// Panics if the grid has no cells.
+    pub fn extents(&self) -> (SignedCoordinate, SignedCoordinate) {
+        assert!(self.width > 0 && self.height > 0, "The grid has no cells");
+        let size = SignedCoordinate::new(self.width as i64 - 1, self.height as i64 - 1);
+        (self.origin, self.origin + size)
+    }
+
+    /// Whether `coord` is inside the grid.
+    pub fn contains(&self, coord: &SignedCoordinate) -> bool {
+        self.index(coord).is_some()
+    }
+
+    fn index(&self, coord: &SignedCoordinate) -> Option<usize> {
+        let x = usize::try_from(coord.x - self.origin.x).ok()?;
+        let y = usize::try_from(coord.y - self.origin.y).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    fn coord(&self, index: usize) -> SignedCoordinate {
+        self.origin + SignedCoordinate::from((index % self.width, index / self.width))
+    }
+
+    /// The data at `coord`, or `None` if the cell is empty or outside of the grid.
+    pub fn get(&self, coord: &SignedCoordinate) -> Option<&Data> {
+        self.cells[self.index(coord)?].as_ref()
+    }
+
+    /// A mutable reference to the data at `coord`, or `None` if the cell is empty or outside of
+    /// the grid.
+    pub fn get_mut(&mut self, coord: &SignedCoordinate) -> Option<&mut Data> {
+        let index = self.index(coord)?;
+        self.cells[index].as_mut()
+    }
+
+    /// Sets the data at `coord`, where `None` empties the cell. Returns the previous data.
+    ///
+    /// Panics if `coord` is outside of the grid.
+    pub fn set(&mut self, coord: SignedCoordinate, data: Option<Data>) -> Option<Data> {
+        let index = self
+            .index(&coord)
+            .unwrap_or_else(|| panic!("{coord} is outside of the grid"));
+        std::mem::replace(&mut self.cells[index], data)
+    }
+
+    /// Every coordinate of the grid in reading order.
+    pub fn coords(&self) -> impl Iterator<Item = SignedCoordinate> + use<Data> {
+        let (origin, width) = (self.origin, self.width);
+        (0..self.cells.len()).map(move |i| origin + SignedCoordinate::from((i % width, i / width)))
+    }
+
+    /// All non-empty cells in reading order.
+    pub fn entries(&self) -> impl Iterator<Item = (SignedCoordinate, &Data)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| Some((self.coord(i), cell.as_ref()?)))
+    }
+}
+
+impl<Data: Clone + CharConvertable> Grid<Data> {
+    /// Reads a grid from the lines of the input, where the first character is at `(0, 0)`.
+    /// Surrounding whitespace is ignored and short lines are padded with empty cells. Fails with
+    /// the position of the first character that `Data` rejects.
+    pub fn read(lines: impl Iterator<Item = impl AsRef<str>>) -> Result<Self, ParseError> {
+        let mut rows = Vec::new();
+        for (y, line) in lines.enumerate() {
+            let line = line.as_ref();
+            let indent = line[..line.len() - line.trim_start().len()].chars().count();
+            let row = line
+                .trim()
+                .chars()
+                .enumerate()
+                .map(|(x, c)| Data::from_char(c).map_err(|e| e.at(y + 1, indent + x + 1)))
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut grid = Self::new(width, rows.len());
+        for (y, row) in rows.into_iter().enumerate() {
+            let start = y * width;
+            for (cell, data) in grid.cells[start..start + width].iter_mut().zip(row) {
+                *cell = data;
+            }
+        }
+        Ok(grid)
+    }
+}
+
+/// Spans the extents of the infinite grid, an infinite grid without extents becomes an empty
+/// grid.
+impl<
+    Data: Clone + CharConvertable + PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool,
+> From<&InfiniteGrid<SignedCoordinate, Data, CACHED_EXTENTS, INCLUDE_EMPTY>> for Grid<Data>
+{
+    fn from(
+        infinite: &InfiniteGrid<SignedCoordinate, Data, CACHED_EXTENTS, INCLUDE_EMPTY>,
+    ) -> Self {
+        let Some((min, max)) = infinite.checked_extents() else {
+            return Self::new(0, 0);
+        };
+        let size = max - min;
+        let mut grid = Self::with_origin(min, size.x as usize + 1, size.y as usize + 1);
+        for (coord, data) in infinite.entries() {
+            grid.set(coord, Some(data));
+        }
+        grid
+    }
+}
+
+/// Keeps the coordinates of every cell. Empty cells are set as well, so an infinite grid that
+/// includes empty cells in its extents keeps the size of the grid.
+impl<
+    Data: Clone + CharConvertable + PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool,
+> From<Grid<Data>> for InfiniteGrid<SignedCoordinate, Data, CACHED_EXTENTS, INCLUDE_EMPTY>
+{
+    fn from(grid: Grid<Data>) -> Self {
+        let mut infinite = Self::new();
+        for (coord, data) in grid.coords().zip(grid.cells) {
+            infinite.set(coord, data);
+        }
+        infinite
+    }
+}
+
+impl<Data: CharConvertable> Debug for Grid<Data> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.width == 0 {
+            return Ok(());
+        }
+        for row in self.cells.chunks(self.width) {
+            for cell in row {
+                write!(f, "{}", Data::to_char(cell.as_ref()))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::{CharConvertable, InfiniteGrid, ParseError, SignedCoordinate};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Digit(u32);
+
+    impl CharConvertable for Digit {
+        fn to_char(option: Option<&Self>) -> char {
+            option.map_or('.', |digit| char::from_digit(digit.0, 10).unwrap())
+        }
+
+        fn from_char(c: char) -> Result<Option<Self>, ParseError> {
+            match c {
+                '.' => Ok(None),
+                c => c
+                    .to_digit(10)
+                    .map(|digit| Some(Digit(digit)))
+                    .ok_or_else(|| ParseError::unexpected_char(c)),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_and_bounds() {
+        let mut grid = Grid::<char>::read(" ab\n c\n\n".lines()).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(format!("{grid:?}"), "ab\nc.\n..\n");
+        assert_eq!(grid.get(&SignedCoordinate::new(1, 0)), Some(&'b'));
+        assert_eq!(grid.get(&SignedCoordinate::new(2, 0)), None);
+        assert_eq!(grid.get(&SignedCoordinate::new(0, -1)), None);
+        assert!(!grid.contains(&SignedCoordinate::new(0, 3)));
+
+        assert_eq!(grid.set(SignedCoordinate::new(1, 2), Some('z')), None);
+        assert_eq!(grid.set(SignedCoordinate::new(0, 0), None), Some('a'));
+        let entries: Vec<_> = grid.entries().map(|(coord, c)| (coord, *c)).collect();
+        assert_eq!(
+            entries,
+            [
+                (SignedCoordinate::new(1, 0), 'b'),
+                (SignedCoordinate::new(0, 1), 'c'),
+                (SignedCoordinate::new(1, 2), 'z'),
+            ]
+        );
+
+        let grid = Grid::<Digit>::read("\n 12\n".lines()).unwrap();
+        assert_eq!(grid.get(&SignedCoordinate::new(1, 1)), Some(&Digit(2)));
+        let error = Grid::<Digit>::read("\n 1x\n".lines()).unwrap_err();
+        assert_eq!((error.line(), error.column()), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn converts_to_and_from_infinite_grids() {
+        let mut infinite =
+            InfiniteGrid::<SignedCoordinate, char, true>::read("#.\n.#".lines()).unwrap();
+        infinite.set(SignedCoordinate::new(-2, -1), Some('@'));
+        let grid = Grid::from(&infinite);
+        assert_eq!(grid.origin(), SignedCoordinate::new(-2, -1));
+        assert_eq!(grid.extents(), infinite.extents());
+        assert_eq!(format!("{grid:?}"), format!("{infinite:?}"));
+
+        let back = InfiniteGrid::<SignedCoordinate, char, true>::from(grid.clone());
+        assert_eq!(back.extents(), infinite.extents());
+        assert_eq!(format!("{back:?}"), format!("{infinite:?}"));
+
+        let with_empty = InfiniteGrid::<SignedCoordinate, char, true, true>::from(Grid::new(3, 2));
+        assert_eq!(
+            with_empty.extents(),
+            (SignedCoordinate::ZERO, SignedCoordinate::new(2, 1))
+        );
+        assert_eq!(Grid::from(&with_empty).width(), 3);
+        let empty = InfiniteGrid::<SignedCoordinate, char, false>::from(Grid::new(3, 2));
+        assert_eq!(Grid::from(&empty).width(), 0);
+    }
+}
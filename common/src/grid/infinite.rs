@@ -0,0 +1,170 @@
+//! A sparse grid that grows in every direction.
+
+use std::fmt::{Debug, Formatter};
+
+use rustc_hash::FxHashMap;
+
+use super::CharConvertable;
+use crate::coord::Coordinate;
+use crate::parse::ParseError;
+
+/// A sparse grid that only stores its non-empty cells, so it can grow in every direction.
+///
+/// With `CACHED_EXTENTS` the bounding box of the grid is kept up to date on every `set` instead
+/// of being computed when needed. `INCLUDE_EMPTY` makes the cached extents include cells that
+/// were set to empty as well, which keeps the borders of the input when drawing the grid.
+///
+/// ```
+/// use aoc_common::{InfiniteGrid, SignedCoordinate};
+///
+/// let mut grid = InfiniteGrid::<SignedCoordinate, char, true>::read("#.\n.#".lines())?;
+/// assert_eq!(grid.get(&SignedCoordinate::new(1, 1)), Some(&'#'));
+/// assert_eq!(grid.get(&SignedCoordinate::new(1, 0)), None);
+///
+/// grid.set(SignedCoordinate::new(-1, 0), Some('@'));
+/// assert_eq!(grid.extents(), (SignedCoordinate::new(-1, 0), SignedCoordinate::new(1, 1)));
+/// assert_eq!(format!("{grid:?}"), "@#.\n..#\n");
+/// # Ok::<(), aoc_common::ParseError>(())
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct InfiniteGrid<
+    Coord: Coordinate,
+    Data: PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool = false,
+> {
+    map: FxHashMap<Coord, Data>,
+    min: Option<Coord>,
+    max: Option<Coord>,
+}
+
+impl<Coord: Coordinate, Data: PartialEq, const CACHED_EXTENTS: bool, const INCLUDE_EMPTY: bool>
+    Default for InfiniteGrid<Coord, Data, CACHED_EXTENTS, INCLUDE_EMPTY>
+{
+    fn default() -> Self {
+        Self {
+            map: FxHashMap::default(),
+            min: None,
+            max: None,
+        }
+    }
+}
+
+impl<
+    Coord: Coordinate,
+    Data: Clone + CharConvertable + PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool,
+> InfiniteGrid<Coord, Data, CACHED_EXTENTS, INCLUDE_EMPTY>
+{
+    /// An empty grid.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a grid from the lines of the input, where the first character is at `(0, 0)`.
+    /// Surrounding whitespace is ignored. Fails with the position of the first character that
+    /// `Data` rejects.
+    pub fn read(lines: impl Iterator<Item = impl AsRef<str>>) -> Result<Self, ParseError> {
+        // Include empty makes no sense if we aren't caching
+        debug_assert!(CACHED_EXTENTS || !INCLUDE_EMPTY);
+        let mut grid = Self::new();
+        for (y, line) in lines.enumerate() {
+            let line = line.as_ref();
+            let indent = line[..line.len() - line.trim_start().len()].chars().count();
+            for (x, c) in line.trim().chars().enumerate() {
+                let data = Data::from_char(c).map_err(|e| e.at(y + 1, indent + x + 1))?;
+                let result = grid.set(Coord::from((x, y)), data);
+                debug_assert!(result.is_none());
+            }
+        }
+        Ok(grid)
+    }
+
+    /// The data at `coord`, or `None` if the cell is empty.
+    pub fn get(&self, coord: &Coord) -> Option<&Data> {
+        self.map.get(coord)
+    }
+
+    /// Sets the data at `coord`, where `None` empties the cell. Returns the previous data.
+    pub fn set(&mut self, coord: Coord, data: Option<Data>) -> Option<Data> {
+        match data {
+            None => {
+                if CACHED_EXTENTS && INCLUDE_EMPTY {
+                    self.min = Some(
+                        self.min
+                            .take()
+                            .map_or_else(|| coord.clone(), |m| m.min(&coord)),
+                    );
+                    self.max = Some(
+                        self.max
+                            .take()
+                            .map_or_else(|| coord.clone(), |m| m.max(&coord)),
+                    );
+                }
+                self.map.remove(&coord)
+            }
+            Some(data) => {
+                if CACHED_EXTENTS {
+                    self.min = Some(
+                        self.min
+                            .take()
+                            .map_or_else(|| coord.clone(), |m| m.min(&coord)),
+                    );
+                    self.max = Some(
+                        self.max
+                            .take()
+                            .map_or_else(|| coord.clone(), |m| m.max(&coord)),
+                    );
+                }
+                self.map.insert(coord, data)
+            }
+        }
+    }
+
+    /// The minimum and maximum coordinates of the grid.
+    ///
+    /// Panics if the grid has never had a cell set.
+    pub fn extents(&self) -> (Coord, Coord) {
+        self.checked_extents().unwrap()
+    }
+
+    /// Like `extents`, but `None` for a grid that has never had a cell set.
+    pub fn checked_extents(&self) -> Option<(Coord, Coord)> {
+        if CACHED_EXTENTS {
+            Some((self.min.clone()?, self.max.clone()?))
+        } else {
+            Some((
+                self.map.keys().cloned().reduce(|a, b| a.min(&b))?,
+                self.map.keys().cloned().reduce(|a, b| a.max(&b))?,
+            ))
+        }
+    }
+
+    /// All non-empty cells, in no particular order.
+    pub fn entries(&self) -> impl Iterator<Item = (Coord, Data)> + '_ {
+        self.map
+            .iter()
+            .map(|(coord, data)| (coord.clone(), data.clone()))
+    }
+}
+
+impl<
+    Coord: Coordinate + Debug,
+    Data: Clone + CharConvertable + PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool,
+> Debug for InfiniteGrid<Coord, Data, CACHED_EXTENTS, INCLUDE_EMPTY>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (min, max) = self.extents();
+        for (nl, coord) in min.range_to_debug(&max) {
+            if nl {
+                writeln!(f, "{}", Data::to_char(self.get(&coord)))?;
+            } else {
+                write!(f, "{}", Data::to_char(self.get(&coord)))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! Grids read from the puzzle input.
+//!
+//! [`InfiniteGrid`] only stores its non-empty cells and can grow in every direction, [`Grid`] is a
+//! dense rectangle for inputs with a fixed size where lookups need to be fast, and [`BitGrid`]
+//! packs a rectangle of present or absent cells into bits so whole lines can be processed at
+//! once.
+
+use crate::parse::ParseError;
+
+mod bits;
+mod dense;
+mod infinite;
+
+pub use bits::{BitGrid, NeighbourCounts};
+pub use dense::Grid;
+pub use infinite::InfiniteGrid;
+
+/// Cell data that can be read from and drawn as a single character of the input.
+pub trait CharConvertable: Sized {
+    /// The character drawn for a cell, where `None` is an empty cell.
+    fn to_char(option: Option<&Self>) -> char;
+
+    /// The cell described by `c`, or `None` if it is empty. Characters that aren't part of the
+    /// format are an error, usually [`ParseError::unexpected_char`], which the grid reader gives a
+    /// position.
+    fn from_char(c: char) -> Result<Option<Self>, ParseError>;
+}
+
+/// Stores every character except `.`, which is empty.
+impl CharConvertable for char {
+    fn to_char(option: Option<&Self>) -> char {
+        match option {
+            None => '.',
+            Some(c) => *c,
+        }
+    }
+
+    fn from_char(c: char) -> Result<Option<Self>, ParseError> {
+        Ok(match c {
+            '.' => None,
+            c => Some(c),
+        })
+    }
+}
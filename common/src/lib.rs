@@ -0,0 +1,65 @@
+//! Solver plumbing and grid utilities shared by the solutions of every year.
+//!
+//! The crate is split into modules by concern:
+//!
+//! - [`runner`]: the [`Day`] solver wrappers and everything needed to run and time them.
+//! - [`grid`]: [`InfiniteGrid`], [`Grid`] and [`BitGrid`], sparse, dense and bit-packed grids
+//!   read from the puzzle input.
+//! - [`coord`]: the [`Coordinate`] trait and [`SignedCoordinate`].
+//! - [`direction`]: [`Facing`], the four directions of a grid.
+//! - [`automaton`]: a cellular automaton engine that runs on any of the grids.
+//! - [`region`]: flood fill, connected components and enclosed pockets of a grid.
+//! - [`search`]: breadth first, Dijkstra and A* searches over grids and arbitrary graphs.
+//! - [`math`]: small number theory helpers.
+//! - [`parse`]: errors for input that doesn't match the format of a puzzle.
+//! - [`net`]: talking to the puzzle site.
+//!
+//! The items a solution needs most are re-exported at the root, so a day usually only needs a
+//! single import:
+//!
+//! ```
+//! use aoc_common::{Day, Lines};
+//!
+//! fn main(lines: Lines) -> anyhow::Result<(usize, usize)> {
+//!     let lines: Vec<_> = lines.collect();
+//!     Ok((lines.len(), lines.iter().map(|line| line.len()).sum()))
+//! }
+//!
+//! let day = Day::CombinedLines(main);
+//! assert_eq!(day.solve("ab\nc")?, ("2".to_owned(), "3".to_owned()));
+//! # use aoc_common::Runnable;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+#![feature(gen_blocks)]
+#![warn(clippy::pedantic, missing_docs)]
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::missing_errors_doc,
+    clippy::missing_panics_doc,
+    clippy::must_use_candidate,
+    clippy::return_self_not_must_use
+)]
+
+pub mod automaton;
+pub mod coord;
+pub mod direction;
+pub mod grid;
+pub mod math;
+pub mod net;
+pub mod parse;
+pub mod region;
+pub mod runner;
+pub mod search;
+#[cfg(test)]
+mod test_util;
+
+pub use coord::{Coordinate, SignedCoordinate};
+pub use direction::Facing;
+pub use grid::{BitGrid, CharConvertable, Grid, InfiniteGrid};
+pub use parse::ParseError;
+pub use runner::{
+    Day, DynDay, Lines, Part, PartAnswer, Parts, Run, Runnable, Solutions, Timing, Verdict, phase,
+};
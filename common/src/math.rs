@@ -0,0 +1,23 @@
+//! Small number theory helpers.
+
+/// Whether `i` is a prime number, by trial division.
+///
+/// ```
+/// use aoc_common::math::is_prime;
+///
+/// let primes: Vec<_> = (0..20).filter(|&i| is_prime(i)).collect();
+/// assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19]);
+/// ```
+pub fn is_prime(i: i64) -> bool {
+    if i < 2 {
+        return false;
+    }
+    let mut j = 2;
+    while j <= i / j {
+        if i % j == 0 {
+            return false;
+        }
+        j += 1;
+    }
+    true
+}
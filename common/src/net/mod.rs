@@ -0,0 +1,380 @@
+//! Talking to the puzzle site: a small HTTP abstraction with a plain HTTP and a `curl` backed
+//! client, the session token, and throttling of requests.
+
+use std::fmt::Write as _;
+use std::fmt::{self, Display, Formatter};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+pub mod percent;
+
+/// The puzzle site.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Overrides the base URL of the puzzle site, e.g. to test against a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+/// Holds the session token.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Points to a file that holds the session token.
+pub const SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+/// The file the session token is read from when neither variable is set.
+pub const DEFAULT_SESSION_FILE: &str = ".session";
+/// Sent with every request unless a client is given another one, as the site asks automated tools
+/// to identify themselves.
+pub const DEFAULT_USER_AGENT: &str = "github.com/superaxander/aoc_2025 by superaxander";
+/// Overrides the user agent, so forks can identify themselves.
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+/// The HTTP methods the site needs.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Method {
+    /// Fetches a page or an input.
+    Get,
+    /// Submits a form.
+    Post,
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Get => write!(f, "GET"),
+            Method::Post => write!(f, "POST"),
+        }
+    }
+}
+
+/// A request to the puzzle site.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Request {
+    /// The HTTP method.
+    pub method: Method,
+    /// The full URL, including the base URL.
+    pub url: String,
+    /// The session token, sent as the `session` cookie.
+    pub session: String,
+    /// A form-urlencoded body, only sent with `Method::Post`.
+    pub body: Option<String>,
+}
+
+/// The response to a [`Request`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Response {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The body of the response.
+    pub body: String,
+}
+
+/// The transport used to talk to the puzzle site, so it can be swapped for a stub in tests.
+pub trait HttpClient: Send + Sync {
+    /// Sends `request` and waits for the whole response. Only transport failures are errors, a
+    /// response with an error status is returned as is.
+    fn send(&self, request: &Request) -> Result<Response>;
+}
+
+/// Picks a client that can handle the scheme of `base_url` and identifies itself as `user_agent`.
+pub fn client_for(base_url: &str, user_agent: &str) -> Box<dyn HttpClient> {
+    if base_url.starts_with("http://") {
+        Box::new(PlainHttpClient::new(user_agent))
+    } else {
+        Box::new(CurlClient::new(user_agent))
+    }
+}
+
+/// A bare-bones HTTP/1.0 client over a plain TCP connection, only supports `http://` URLs.
+#[derive(Clone, Debug)]
+pub struct PlainHttpClient {
+    user_agent: String,
+}
+
+impl PlainHttpClient {
+    /// A client that sends `user_agent` with every request.
+    pub fn new(user_agent: impl Into<String>) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+        }
+    }
+}
+
+impl Default for PlainHttpClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_USER_AGENT)
+    }
+}
+
+impl HttpClient for PlainHttpClient {
+    fn send(&self, request: &Request) -> Result<Response> {
+        let rest = request
+            .url
+            .strip_prefix("http://")
+            .ok_or_else(|| anyhow!("Only http:// URLs are supported: {}", request.url))?;
+        let (authority, path) = rest.find('/').map_or((rest, "/"), |i| rest.split_at(i));
+        let address = if authority.contains(':') {
+            authority.to_owned()
+        } else {
+            format!("{authority}:80")
+        };
+
+        let mut stream = TcpStream::connect(&address)
+            .with_context(|| format!("Could not connect to {address}"))?;
+        let mut head = format!(
+            "{} {path} HTTP/1.0\r\nHost: {authority}\r\nUser-Agent: {}\r\nCookie: session={}\r\n",
+            request.method, self.user_agent, request.session
+        );
+        if let Some(body) = &request.body {
+            head.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+            write!(head, "Content-Length: {}\r\n", body.len())?;
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        if let Some(body) = &request.body {
+            stream.write_all(body.as_bytes())?;
+        }
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| anyhow!("Malformed response from {address}"))?;
+        let status = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| anyhow!("Malformed status line from {address}"))?;
+        Ok(Response {
+            status,
+            body: body.to_owned(),
+        })
+    }
+}
+
+/// Uses the `curl` binary, which takes care of TLS. The session cookie is passed through a config
+/// on stdin so it doesn't show up in the process list.
+#[derive(Clone, Debug)]
+pub struct CurlClient {
+    user_agent: String,
+}
+
+impl CurlClient {
+    /// A client that sends `user_agent` with every request.
+    pub fn new(user_agent: impl Into<String>) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+        }
+    }
+}
+
+impl Default for CurlClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_USER_AGENT)
+    }
+}
+
+impl HttpClient for CurlClient {
+    fn send(&self, request: &Request) -> Result<Response> {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--config", "-"])
+            .args(["--user-agent", &self.user_agent])
+            .args(["--write-out", "\n%{http_code}"]);
+        if let Some(body) = &request.body {
+            command.args(["--data-raw", body]);
+        }
+        command
+            .arg(&request.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = command.spawn().context("Could not run curl")?;
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(format!("cookie = \"session={}\"\n", request.session).as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!(
+                "curl failed for {}: {}",
+                request.url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let output = String::from_utf8(output.stdout)?;
+        let (body, status) = output
+            .rsplit_once('\n')
+            .ok_or_else(|| anyhow!("Missing status code in curl output"))?;
+        Ok(Response {
+            status: status.trim().parse()?,
+            body: body.to_owned(),
+        })
+    }
+}
+
+/// Reads the session token from `$AOC_SESSION`, or otherwise from the file at `$AOC_SESSION_FILE`
+/// or `.session`.
+pub fn load_session() -> Result<String> {
+    if let Ok(session) = std::env::var(SESSION_ENV) {
+        return Ok(session.trim().to_owned());
+    }
+    let path = std::env::var_os(SESSION_FILE_ENV)
+        .map_or_else(|| PathBuf::from(DEFAULT_SESSION_FILE), PathBuf::from);
+    let session = std::fs::read_to_string(&path).with_context(|| {
+        format!(
+            "No session token, set ${SESSION_ENV} or store it in {}",
+            path.display()
+        )
+    })?;
+    Ok(session.trim().to_owned())
+}
+
+/// Enforces a minimum interval between requests. The time of the last request is also stored on
+/// disk so separate runs of the binary don't add up to a burst of requests.
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    path: Option<PathBuf>,
+    last: Mutex<Option<SystemTime>>,
+}
+
+impl Throttle {
+    /// Allows a request every `interval`, sharing the time of the last request through the file
+    /// at `path` if one is given.
+    pub fn new(interval: Duration, path: Option<PathBuf>) -> Self {
+        Self {
+            interval,
+            path,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Waits until the next request is allowed and then runs it, holding the throttle so
+    /// concurrent requests are spaced out as well.
+    pub fn run<T>(&self, func: impl FnOnce() -> Result<T>) -> Result<T> {
+        let mut last = self.last.lock().unwrap();
+        let previous = last.or_else(|| self.path.as_deref().and_then(read_timestamp));
+        if let Some(previous) = previous {
+            let elapsed = previous.elapsed().unwrap_or_default();
+            if let Some(remaining) = self.interval.checked_sub(elapsed) {
+                thread::sleep(remaining);
+            }
+        }
+
+        let now = SystemTime::now();
+        *last = Some(now);
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let millis = now.duration_since(UNIX_EPOCH)?.as_millis();
+            std::fs::write(path, millis.to_string())?;
+        }
+        func()
+    }
+}
+
+fn read_timestamp(path: &Path) -> Option<SystemTime> {
+    let millis = std::fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+/// A local HTTP server for testing code that talks to the puzzle site. Enabled by the
+/// `test-support` feature.
+#[cfg(any(test, feature = "test-support"))]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A request as seen by the stub server.
+    #[derive(Clone, Debug)]
+    pub struct Received {
+        /// The request method, like `GET`.
+        pub method: String,
+        /// The path of the request, including the query.
+        pub path: String,
+        /// The header lines, in the order they were sent.
+        pub headers: Vec<String>,
+        /// The request body, empty if there was none.
+        pub body: String,
+    }
+
+    /// A local HTTP server that answers every request through `respond` and records what it got.
+    pub struct StubServer {
+        /// The `http://` URL the server listens on.
+        pub base_url: String,
+        /// Every request received so far.
+        pub received: Arc<Mutex<Vec<Received>>>,
+    }
+
+    impl StubServer {
+        /// Starts the server on a free local port. `respond` gives the status and body of the
+        /// response to each request.
+        pub fn start(respond: impl Fn(&Received) -> (u16, String) + Send + 'static) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let received = Arc::new(Mutex::new(Vec::new()));
+
+            let log = Arc::clone(&received);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { break };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let mut parts = line.split_whitespace();
+                    let method = parts.next().unwrap_or_default().to_owned();
+                    let path = parts.next().unwrap_or_default().to_owned();
+
+                    let mut headers = Vec::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        let header = header.trim_end().to_owned();
+                        if header.is_empty() {
+                            break;
+                        }
+                        if let Some(length) = header.strip_prefix("Content-Length: ") {
+                            content_length = length.parse().unwrap();
+                        }
+                        headers.push(header);
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+
+                    let request = Received {
+                        method,
+                        path,
+                        headers,
+                        body: String::from_utf8(body).unwrap(),
+                    };
+                    let (status, body) = respond(&request);
+                    log.lock().unwrap().push(request);
+                    write!(
+                        stream,
+                        "HTTP/1.0 {status} Stub\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                }
+            });
+
+            Self { base_url, received }
+        }
+
+        /// A snapshot of the requests received so far.
+        pub fn requests(&self) -> Vec<Received> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+}
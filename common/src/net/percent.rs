@@ -0,0 +1,157 @@
+//! Percent-encoding as described in RFC 3986, and the `application/x-www-form-urlencoded` variant
+//! used for request bodies and query strings. Text is encoded byte by byte as UTF-8.
+
+use std::fmt::Write;
+
+use anyhow::{Result, anyhow, bail};
+
+/// Whether `byte` is in the RFC 3986 unreserved set, which never needs encoding.
+pub fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Encodes every byte outside the unreserved set as `%XX`, so the result is safe anywhere in a
+/// URL.
+pub fn encode(string: &str) -> String {
+    encode_with(string, false)
+}
+
+/// Like `encode`, but encodes spaces as `+` as form-urlencoded data expects.
+pub fn encode_form_component(string: &str) -> String {
+    encode_with(string, true)
+}
+
+fn encode_with(string: &str, plus_for_space: bool) -> String {
+    let mut encoded = String::with_capacity(string.len());
+    for byte in string.bytes() {
+        if is_unreserved(byte) {
+            encoded.push(byte as char);
+        } else if plus_for_space && byte == b' ' {
+            encoded.push('+');
+        } else {
+            write!(encoded, "%{byte:02X}").unwrap();
+        }
+    }
+    encoded
+}
+
+/// Decodes all `%XX` escapes, failing on malformed escapes or if the decoded bytes aren't valid
+/// UTF-8. Other characters are passed through unchanged.
+pub fn decode(string: &str) -> Result<String> {
+    decode_with(string, false)
+}
+
+/// Like `decode`, but also turns `+` back into a space.
+pub fn decode_form_component(string: &str) -> Result<String> {
+    decode_with(string, true)
+}
+
+fn decode_with(string: &str, plus_for_space: bool) -> Result<String> {
+    let mut decoded = Vec::with_capacity(string.len());
+    let mut bytes = string.bytes().enumerate();
+    while let Some((i, byte)) = bytes.next() {
+        match byte {
+            b'%' => {
+                let value = string
+                    .get(i + 1..i + 3)
+                    .filter(|escape| escape.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|escape| u8::from_str_radix(escape, 16).ok())
+                    .ok_or_else(|| anyhow!("Invalid escape at {i} in {string:?}"))?;
+                decoded.push(value);
+                bytes.nth(1);
+            }
+            b'+' if plus_for_space => decoded.push(b' '),
+            _ => decoded.push(byte),
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| anyhow!("{string:?} doesn't decode to valid UTF-8"))
+}
+
+/// Builds a form-urlencoded string like `level=1&answer=42` from name/value pairs.
+pub fn encode_form<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let mut encoded = String::new();
+    for (name, value) in pairs {
+        if !encoded.is_empty() {
+            encoded.push('&');
+        }
+        encoded.push_str(&encode_form_component(name));
+        encoded.push('=');
+        encoded.push_str(&encode_form_component(value));
+    }
+    encoded
+}
+
+/// Splits a form-urlencoded string back into name/value pairs. A pair without `=` has an empty
+/// value.
+pub fn decode_form(string: &str) -> Result<Vec<(String, String)>> {
+    string
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            if value.contains('=') {
+                bail!("Invalid form pair: {pair:?}");
+            }
+            Ok((decode_form_component(name)?, decode_form_component(value)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, decode_form, decode_form_component, encode, encode_form};
+    use crate::test_util::Rng;
+
+    /// A short string mixing characters that are safe, reserved and outside of ASCII.
+    fn random_string(rng: &mut Rng) -> String {
+        const CHARS: &[char] = &[
+            'a', 'Z', '0', '9', '-', '.', '_', '~', ' ', '+', '%', '&', '=', '?', '/', '#', '\n',
+            '\t', '\0', 'é', 'ß', '€', '✓', '𝄞', '🎄',
+        ];
+        let len = rng.next() % 16;
+        (0..len)
+            .map(|_| CHARS[(rng.next() % CHARS.len() as u64) as usize])
+            .collect()
+    }
+
+    #[test]
+    fn known_encodings() {
+        assert_eq!(encode("AZaz09-._~"), "AZaz09-._~");
+        assert_eq!(
+            encode("a b&c=d/e?f#g%h+"),
+            "a%20b%26c%3Dd%2Fe%3Ff%23g%25h%2B"
+        );
+        assert_eq!(encode("é€𝄞"), "%C3%A9%E2%82%AC%F0%9D%84%9E");
+        assert_eq!(
+            encode_form([("level", "1"), ("answer", "a b+c")]),
+            "level=1&answer=a+b%2Bc"
+        );
+
+        assert_eq!(decode("%c3%A9%20x").unwrap(), "é x");
+        assert_eq!(decode("a+b").unwrap(), "a+b");
+        assert_eq!(decode_form_component("a+b%2B").unwrap(), "a b+");
+        assert!(decode("%").is_err());
+        assert!(decode("%2").is_err());
+        assert!(decode("%+1").is_err());
+        assert!(decode("%zz").is_err());
+        assert!(decode("%C3").is_err());
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut rng = Rng::new(0x2025_1201);
+        for _ in 0..1000 {
+            let string = random_string(&mut rng);
+            let encoded = encode(&string);
+            assert!(encoded.bytes().all(|b| b.is_ascii_graphic()), "{encoded}");
+            assert_eq!(decode(&encoded).unwrap(), string);
+
+            let pairs = [
+                (random_string(&mut rng), random_string(&mut rng)),
+                (random_string(&mut rng), string),
+            ];
+            let form = encode_form(pairs.iter().map(|(n, v)| (n.as_str(), v.as_str())));
+            assert_eq!(decode_form(&form).unwrap(), pairs);
+        }
+    }
+}
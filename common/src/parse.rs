@@ -0,0 +1,253 @@
+//! Errors for input that doesn't match the format of a puzzle, pointing at where in the input the
+//! problem is.
+//!
+//! Solvers return [`ParseError`]s through `anyhow`, so they reach the runner like any other error.
+//! The runner then fills in the day with [`attach_day`].
+//!
+//! ```
+//! use aoc_common::parse::{self, ParseError};
+//!
+//! let line = "L68 R4x";
+//! let error = parse::number::<u32>(&line[5..])
+//!     .map_err(|e| e.in_line(3, line, &line[5..]))
+//!     .unwrap_err();
+//! assert_eq!(error.line(), Some(3));
+//! assert_eq!(error.column(), Some(6));
+//! assert_eq!(error.to_string(), "Invalid number \"4x\" at line 3, column 6");
+//! ```
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// What is wrong with the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A character that isn't part of the format.
+    UnexpectedChar(char),
+    /// Text that should have been a number.
+    InvalidNumber,
+    /// Text that isn't part of the format, like an unknown operator.
+    UnexpectedToken,
+    /// Something the format requires is missing, like a separator.
+    Missing(&'static str),
+}
+
+/// Input that doesn't match the format of a puzzle, with the offending text and as much of its
+/// position as is known. Lines and columns count from 1, columns count characters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    text: String,
+    day: Option<u32>,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl ParseError {
+    /// An error of `kind` about `text`, without a position.
+    pub fn new(kind: ParseErrorKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            day: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// `c` isn't part of the format.
+    pub fn unexpected_char(c: char) -> Self {
+        Self::new(ParseErrorKind::UnexpectedChar(c), c)
+    }
+
+    /// `text` isn't part of the format.
+    pub fn unexpected(text: impl Into<String>) -> Self {
+        Self::new(ParseErrorKind::UnexpectedToken, text)
+    }
+
+    /// `text` is missing `what`.
+    pub fn missing(what: &'static str, text: impl Into<String>) -> Self {
+        Self::new(ParseErrorKind::Missing(what), text)
+    }
+
+    /// Sets the position of the error.
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the position of the error to where `text` starts in line number `number`, where `text`
+    /// is a slice of `line`. Only the line is set if it isn't.
+    pub fn in_line(mut self, number: usize, line: &str, text: &str) -> Self {
+        self.line = Some(number);
+        self.column = offset(line, text).map(|offset| line[..offset].chars().count() + 1);
+        self
+    }
+
+    /// Sets the position of the error to where `text` starts in `input`, where `text` is a slice
+    /// of `input`. Nothing is set if it isn't.
+    pub fn locate(self, input: &str, text: &str) -> Self {
+        let Some(offset) = offset(input, text) else {
+            return self;
+        };
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        self.at(line, column)
+    }
+
+    /// Sets the day the error happened in, unless it is already set.
+    pub fn for_day(mut self, day: u32) -> Self {
+        self.day.get_or_insert(day);
+        self
+    }
+
+    /// What is wrong with the input.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// The offending text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The day whose input this is, if known.
+    pub fn day(&self) -> Option<u32> {
+        self.day
+    }
+
+    /// The line of the offending text, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The column of the offending text, if known.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "Unexpected char {c:?}")?,
+            ParseErrorKind::InvalidNumber => write!(f, "Invalid number {:?}", self.text)?,
+            ParseErrorKind::UnexpectedToken => write!(f, "Unexpected {:?}", self.text)?,
+            ParseErrorKind::Missing(what) => write!(f, "Missing {what} in {:?}", self.text)?,
+        }
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " at line {line}, column {column}")?,
+            (Some(line), None) => write!(f, " at line {line}")?,
+            _ => {}
+        }
+        if let Some(day) = self.day {
+            write!(f, " of day {day}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// The byte offset of `text` in `input` if `text` is a slice of it.
+fn offset(input: &str, text: &str) -> Option<usize> {
+    let offset = (text.as_ptr() as usize).checked_sub(input.as_ptr() as usize)?;
+    (offset + text.len() <= input.len()).then_some(offset)
+}
+
+/// Parses `text` as a number.
+///
+/// ```
+/// use aoc_common::parse::{self, ParseErrorKind};
+///
+/// assert_eq!(parse::number::<i64>("-12"), Ok(-12));
+/// assert_eq!(parse::number::<u8>("300").unwrap_err().kind(), &ParseErrorKind::InvalidNumber);
+/// ```
+pub fn number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(ParseErrorKind::InvalidNumber, text))
+}
+
+/// Fills in `day` on the [`ParseError`] behind `error`, if it is one.
+pub fn attach_day(mut error: anyhow::Error, day: u32) -> anyhow::Error {
+    if let Some(parse_error) = error.downcast_mut::<ParseError>() {
+        parse_error.day.get_or_insert(day);
+    }
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{Context, anyhow};
+
+    use super::{ParseError, attach_day};
+    use crate::{CharConvertable, InfiniteGrid, SignedCoordinate};
+
+    #[derive(Clone, PartialEq)]
+    struct Wall;
+
+    impl CharConvertable for Wall {
+        fn to_char(option: Option<&Self>) -> char {
+            if option.is_some() { '#' } else { '.' }
+        }
+
+        fn from_char(c: char) -> Result<Option<Self>, ParseError> {
+            match c {
+                '.' => Ok(None),
+                '#' => Ok(Some(Wall)),
+                c => Err(ParseError::unexpected_char(c)),
+            }
+        }
+    }
+
+    #[test]
+    fn positions() {
+        let input = "ab\ncé\nxyz";
+        let error = ParseError::unexpected("z").locate(input, &input[9..]);
+        assert_eq!((error.line(), error.column()), (Some(3), Some(3)));
+        let error = ParseError::unexpected("é").locate(input, &input[4..]);
+        assert_eq!((error.line(), error.column()), (Some(2), Some(2)));
+
+        let error = ParseError::unexpected("z").locate(input, "z");
+        assert_eq!((error.line(), error.column()), (None, None));
+        let error = ParseError::unexpected("z").in_line(7, input, "z");
+        assert_eq!((error.line(), error.column()), (Some(7), None));
+    }
+
+    #[test]
+    fn grid_positions() {
+        let error = InfiniteGrid::<SignedCoordinate, Wall, false>::read("#.\n  .#?\n".lines())
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "Unexpected char '?' at line 2, column 5");
+        assert!(InfiniteGrid::<SignedCoordinate, Wall, false>::read("#.\n.#".lines()).is_ok());
+    }
+
+    #[test]
+    fn attaches_day() {
+        let error = attach_day(ParseError::unexpected_char('?').at(2, 5).into(), 4);
+        assert_eq!(
+            error.to_string(),
+            "Unexpected char '?' at line 2, column 5 of day 4"
+        );
+
+        let error = attach_day(
+            Err::<(), _>(ParseError::missing("'-'", "12"))
+                .context("Ranges")
+                .unwrap_err(),
+            4,
+        );
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Missing '-' in \"12\" of day 4"
+        );
+
+        let error = attach_day(anyhow!("Other"), 4);
+        assert_eq!(error.to_string(), "Other");
+    }
+}
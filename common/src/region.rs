@@ -0,0 +1,236 @@
+//! Regions of connected cells of an [`InfiniteGrid`]: flood fill, connected components, and
+//! pockets that are enclosed by the rest of the grid.
+//!
+//! ```
+//! use aoc_common::{InfiniteGrid, SignedCoordinate};
+//!
+//! let input = "AAAA\nBBCD\nBBCC\nEEEC";
+//! let garden = InfiniteGrid::<SignedCoordinate, char, true>::read(input.lines())?;
+//! let regions = garden.components(false);
+//! let price: usize = regions.iter().map(|r| r.area() * r.perimeter()).sum();
+//! let discount: usize = regions.iter().map(|r| r.area() * r.sides()).sum();
+//! assert_eq!((price, discount), (140, 80));
+//! # Ok::<(), aoc_common::ParseError>(())
+//! ```
+
+use rustc_hash::FxHashSet;
+
+use crate::coord::SignedCoordinate;
+use crate::grid::{CharConvertable, InfiniteGrid};
+use crate::search::{bfs, passable_neighbours};
+
+/// A set of cells, usually connected.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Region {
+    cells: FxHashSet<SignedCoordinate>,
+}
+
+impl Region {
+    /// A region of `cells`.
+    pub fn new(cells: impl IntoIterator<Item = SignedCoordinate>) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+        }
+    }
+
+    /// Whether `coord` is part of the region.
+    pub fn contains(&self, coord: &SignedCoordinate) -> bool {
+        self.cells.contains(coord)
+    }
+
+    /// The cells of the region, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = SignedCoordinate> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// The number of cells.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges between the region and the cells outside of it.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|cell| cell.neighbours::<false>().collect::<Vec<_>>())
+            .filter(|n| !self.contains(n))
+            .count()
+    }
+
+    /// The number of straight sides of the outline of the region, including the outlines of its
+    /// holes. A polygon has as many sides as corners, so this counts the corners of every cell.
+    pub fn sides(&self) -> usize {
+        let mut corners = 0;
+        for cell in &self.cells {
+            for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                let horizontal = self.contains(&cell.east(dx));
+                let vertical = self.contains(&cell.south(dy));
+                let diagonal = self.contains(&cell.east(dx).south(dy));
+                // An outside corner, or an inside corner where the region turns around the
+                // diagonal cell
+                if !horizontal && !vertical || horizontal && vertical && !diagonal {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+}
+
+impl<
+    Data: Clone + CharConvertable + PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool,
+> InfiniteGrid<SignedCoordinate, Data, CACHED_EXTENTS, INCLUDE_EMPTY>
+{
+    /// The cells inside the extents of the grid that can be reached from `seed` through cells for
+    /// which `passable` holds, where `passable` gets `None` for empty cells. The region is empty if
+    /// `seed` itself isn't passable or outside of the extents.
+    pub fn flood_fill(
+        &self,
+        seed: SignedCoordinate,
+        include_diagonal: bool,
+        passable: impl Fn(Option<&Data>) -> bool,
+    ) -> Region {
+        let inside = self.checked_extents().is_some_and(|(min, max)| {
+            (min.x..=max.x).contains(&seed.x) && (min.y..=max.y).contains(&seed.y)
+        });
+        if !inside || !passable(self.get(&seed)) {
+            return Region::default();
+        }
+        let neighbours = passable_neighbours(self, include_diagonal, passable);
+        Region::new(bfs(seed, neighbours, |_| false).reached().map(|(c, _)| *c))
+    }
+
+    /// The connected components of non-empty cells with the same data, in the reading order of
+    /// their first cell, so the index of a region is a stable label.
+    pub fn components(&self, include_diagonal: bool) -> Vec<Region> {
+        let mut cells: Vec<_> = self.entries().collect();
+        cells.sort_unstable_by_key(|(coord, _)| (coord.y, coord.x));
+
+        let mut labelled = FxHashSet::default();
+        let mut regions = Vec::new();
+        for (coord, data) in cells {
+            if labelled.contains(&coord) {
+                continue;
+            }
+            let region = self.flood_fill(coord, include_diagonal, |cell| cell == Some(&data));
+            labelled.extend(region.cells());
+            regions.push(region);
+        }
+        regions
+    }
+
+    /// The pockets of cells inside the extents for which `passable` holds, but which can't be
+    /// reached from outside of the extents through such cells. Everything outside of the extents
+    /// is passable. The pockets are in the reading order of their first cell.
+    pub fn enclosed(
+        &self,
+        include_diagonal: bool,
+        passable: impl Fn(Option<&Data>) -> bool,
+    ) -> Vec<Region> {
+        let Some((min, max)) = self.checked_extents() else {
+            return Vec::new();
+        };
+        // A ring of cells around the extents connects everything that can escape
+        let (outer_min, outer_max) = (min.north(1).west(1), max.south(1).east(1));
+        let is_open = |coord: &SignedCoordinate| {
+            let inside = (min.x..=max.x).contains(&coord.x) && (min.y..=max.y).contains(&coord.y);
+            !inside || passable(self.get(coord))
+        };
+        let neighbours = |coord: &SignedCoordinate| {
+            let neighbours: Vec<_> = if include_diagonal {
+                coord.neighbours::<true>().collect()
+            } else {
+                coord.neighbours::<false>().collect()
+            };
+            neighbours.into_iter().filter(|n| {
+                (outer_min.x..=outer_max.x).contains(&n.x)
+                    && (outer_min.y..=outer_max.y).contains(&n.y)
+                    && is_open(n)
+            })
+        };
+        let outside: FxHashSet<_> = bfs(outer_min, neighbours, |_| false)
+            .reached()
+            .map(|(coord, _)| *coord)
+            .collect();
+
+        let mut pockets: Vec<Region> = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let coord = SignedCoordinate::new(x, y);
+                if outside.contains(&coord)
+                    || !is_open(&coord)
+                    || pockets.iter().any(|pocket| pocket.contains(&coord))
+                {
+                    continue;
+                }
+                pockets.push(self.flood_fill(coord, include_diagonal, &passable));
+            }
+        }
+        pockets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Region;
+    use crate::{InfiniteGrid, SignedCoordinate};
+
+    #[test]
+    fn areas_perimeters_and_sides() {
+        let garden =
+            InfiniteGrid::<SignedCoordinate, char, true>::read("AAAA\nBBCD\nBBCC\nEEEC".lines())
+                .unwrap();
+        let measures: Vec<_> = garden
+            .components(false)
+            .iter()
+            .map(|region| (region.area(), region.perimeter(), region.sides()))
+            .collect();
+        assert_eq!(
+            measures,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+
+        // A ring has the sides of its hole as well
+        let ring = Region::new(
+            SignedCoordinate::ZERO
+                .neighbours::<true>()
+                .map(|c| c + SignedCoordinate::new(1, 1)),
+        );
+        assert_eq!((ring.area(), ring.perimeter(), ring.sides()), (8, 16, 8));
+
+        // Diagonally touching cells are one region with 8-connectivity only
+        let diagonal =
+            InfiniteGrid::<SignedCoordinate, char, true>::read("#.\n.#".lines()).unwrap();
+        assert_eq!(diagonal.components(false).len(), 2);
+        assert_eq!(diagonal.components(true).len(), 1);
+    }
+
+    #[test]
+    fn flood_fill_and_enclosed() {
+        let grid = InfiniteGrid::<SignedCoordinate, char, true, true>::read(
+            "#####.\n#..#..\n#..#.#\n####..\n...#..\n..#.#.\n...#..".lines(),
+        )
+        .unwrap();
+        let empty = |cell: Option<&char>| cell.is_none();
+
+        let room = grid.flood_fill(SignedCoordinate::new(1, 1), false, empty);
+        assert_eq!(room.area(), 4);
+        assert!(!room.contains(&SignedCoordinate::new(4, 1)));
+        assert_eq!(
+            grid.flood_fill(SignedCoordinate::ZERO, false, empty).area(),
+            0
+        );
+        assert_eq!(
+            grid.flood_fill(SignedCoordinate::new(9, 9), false, empty)
+                .area(),
+            0
+        );
+
+        let pockets = grid.enclosed(false, empty);
+        assert_eq!(pockets, [room, Region::new([SignedCoordinate::new(3, 5)])]);
+        // The pocket at the bottom escapes diagonally
+        assert_eq!(grid.enclosed(true, empty).len(), 1);
+    }
+}
@@ -0,0 +1,412 @@
+//! Running solvers: the [`Day`] wrappers around a day's solver functions, and the answers and
+//! timings a run produces.
+
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+
+/// One of the two parts of a puzzle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Part {
+    /// The first part.
+    A,
+    /// The second part, which unlocks once the first is solved.
+    B,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::A, Part::B];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+/// Which parts of a puzzle to run.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Parts {
+    /// Only part a.
+    A,
+    /// Only part b.
+    B,
+    /// Part a and then part b.
+    Both,
+}
+
+impl From<Part> for Parts {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::A => Parts::A,
+            Part::B => Parts::B,
+        }
+    }
+}
+
+impl Parts {
+    /// Whether part a is run.
+    pub fn a(self) -> bool {
+        matches!(self, Parts::A | Parts::Both)
+    }
+
+    /// Whether part b is run.
+    pub fn b(self) -> bool {
+        matches!(self, Parts::B | Parts::Both)
+    }
+}
+
+/// The formatted answers for the parts of a day that were run or are known.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Solutions {
+    /// The answer to part a.
+    pub a: Option<String>,
+    /// The answer to part b.
+    pub b: Option<String>,
+}
+
+impl Solutions {
+    /// The answer to `part`.
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::A => self.a.as_deref(),
+            Part::B => self.b.as_deref(),
+        }
+    }
+
+    /// The parts that have an answer, with their answers.
+    pub fn iter(&self) -> impl Iterator<Item = (Part, &str)> {
+        Part::ALL
+            .into_iter()
+            .filter_map(|part| Some((part, self.get(part)?)))
+    }
+}
+
+/// How an answer compares to the known answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    /// The answer matches.
+    Correct,
+    /// The answer differs from the `expected` one.
+    Wrong {
+        /// The known answer.
+        expected: String,
+    },
+    /// There is no known answer to compare with.
+    Unknown,
+}
+
+impl Verdict {
+    /// Compares `actual` with the `expected` answer, if it is known.
+    pub fn check(expected: Option<&str>, actual: &str) -> Self {
+        match expected {
+            None => Verdict::Unknown,
+            Some(expected) if expected == actual => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+}
+
+/// The lines of a puzzle input, for solvers that only ever iterate over the input line by line.
+pub type Lines<'a> = std::str::Lines<'a>;
+
+/// The answer of a `Day::BoolSeparated` solver, which solves part a or part b depending on its
+/// flag and can therefore return either answer type.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PartAnswer<SolutionA, SolutionB> {
+    /// The answer to part a.
+    A(SolutionA),
+    /// The answer to part b.
+    B(SolutionB),
+}
+
+impl<SolutionA: Display, SolutionB: Display> Display for PartAnswer<SolutionA, SolutionB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartAnswer::A(solution) => solution.fmt(f),
+            PartAnswer::B(solution) => solution.fmt(f),
+        }
+    }
+}
+
+/// The solver of a day, in the shape that suits it best. Answers can be of any type that can be
+/// displayed, they are compared and reported in their formatted form.
+///
+/// ```
+/// use aoc_common::{Day, Parts, Runnable};
+///
+/// let day = Day::Separated(|input| Ok(input.len()), |input| Ok(input.to_uppercase()));
+/// let run = day.run("abc", Parts::B)?;
+/// assert_eq!(run.solutions.a, None);
+/// assert_eq!(run.solutions.b.as_deref(), Some("ABC"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub enum Day<SolutionA: Display, SolutionB: Display> {
+    /// Solves both parts at once, for days where part b builds on the work of part a. Use
+    /// [`phase`] to time the steps of the solver.
+    Combined(fn(&str) -> Result<(SolutionA, SolutionB)>),
+    /// Like `Combined`, but takes the input line by line.
+    CombinedLines(fn(Lines) -> Result<(SolutionA, SolutionB)>),
+    /// Solves each part with its own function.
+    Separated(fn(&str) -> Result<SolutionA>, fn(&str) -> Result<SolutionB>),
+    /// Solves either part with a single function, which is told whether to solve part b.
+    BoolSeparated(fn(&str, bool) -> Result<PartAnswer<SolutionA, SolutionB>>),
+}
+
+/// A day with its answer types erased, so days with different answer types can be stored
+/// together.
+pub type DynDay = dyn Runnable + Send + Sync;
+
+impl<SolutionA: Display + 'static, SolutionB: Display + 'static> From<Day<SolutionA, SolutionB>>
+    for Box<DynDay>
+{
+    fn from(day: Day<SolutionA, SolutionB>) -> Self {
+        Box::new(day)
+    }
+}
+
+impl<SolutionA: Display, SolutionB: Display> Day<SolutionA, SolutionB> {
+    fn run_with_result(&self, input: &str, parts: Parts) -> Result<Run> {
+        let mut run = Run::default();
+        match self {
+            Day::Combined(func) => return Self::run_combined(parts, || func(input)),
+            Day::CombinedLines(func) => return Self::run_combined(parts, || func(input.lines())),
+            Day::Separated(func_a, func_b) => {
+                if parts.a() {
+                    let now = Instant::now();
+                    run.solutions.a = Some(func_a(input)?.to_string());
+                    run.timings.push(Timing::new("Part a", now.elapsed()));
+                }
+                if parts.b() {
+                    let now = Instant::now();
+                    run.solutions.b = Some(func_b(input)?.to_string());
+                    run.timings.push(Timing::new("Part b", now.elapsed()));
+                }
+            }
+            Day::BoolSeparated(func) => {
+                if parts.a() {
+                    let now = Instant::now();
+                    run.solutions.a = Some(func(input, false)?.to_string());
+                    run.timings.push(Timing::new("Part a", now.elapsed()));
+                }
+                if parts.b() {
+                    let now = Instant::now();
+                    run.solutions.b = Some(func(input, true)?.to_string());
+                    run.timings.push(Timing::new("Part b", now.elapsed()));
+                }
+            }
+        }
+        Ok(run)
+    }
+
+    fn run_combined(
+        parts: Parts,
+        func: impl FnOnce() -> Result<(SolutionA, SolutionB)>,
+    ) -> Result<Run> {
+        let now = Instant::now();
+        let previous = PHASES.replace(Some(Vec::new()));
+        let result = func();
+        let end = Instant::now();
+        let phases = PHASES.replace(previous).unwrap_or_default();
+        let (solution_a, solution_b) = result?;
+
+        let mut timings = vec![Timing::new("Combined parts", end - now)];
+        for (i, (name, start)) in phases.iter().enumerate() {
+            let start = if i == 0 { now } else { *start };
+            let until = phases.get(i + 1).map_or(end, |(_, next)| *next);
+            timings.push(Timing::new(*name, until - start));
+        }
+
+        Ok(Run {
+            solutions: Solutions {
+                a: parts.a().then(|| solution_a.to_string()),
+                b: parts.b().then(|| solution_b.to_string()),
+            },
+            timings,
+        })
+    }
+}
+
+thread_local! {
+    static PHASES: RefCell<Option<Vec<(&'static str, Instant)>>> = const { RefCell::new(None) };
+}
+
+/// Starts a new named phase (e.g. "Parse", "Part a") of the combined solver that is currently
+/// running, ending the previous one. The runner reports how long each phase took, where any time
+/// before the first phase counts towards it. Does nothing when called outside of the runner.
+pub fn phase(name: &'static str) {
+    PHASES.with_borrow_mut(|phases| {
+        if let Some(phases) = phases {
+            phases.push((name, Instant::now()));
+        }
+    });
+}
+
+/// How long a part or phase of a run took.
+#[derive(Clone, Debug)]
+pub struct Timing {
+    /// What was timed, like "Part a" or the name of a phase.
+    pub name: String,
+    /// How long it took.
+    pub duration: Duration,
+}
+
+impl Timing {
+    /// Creates a timing of `duration` for `name`.
+    pub fn new(name: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            name: name.into(),
+            duration,
+        }
+    }
+}
+
+/// The answers of a single run of a day together with how long each part took.
+#[derive(Clone, Debug, Default)]
+pub struct Run {
+    /// The answers of the parts that were run.
+    pub solutions: Solutions,
+    /// The timings in the order they were taken.
+    pub timings: Vec<Timing>,
+}
+
+/// A solver that can be run on an input, implemented by [`Day`].
+pub trait Runnable {
+    /// Runs `parts` on `input`, timing each of them.
+    fn run(&self, input: &str, parts: Parts) -> Result<Run>;
+
+    /// Solves both parts, formatting the answers as they would be printed.
+    fn solve(&self, input: &str) -> Result<(String, String)>;
+}
+
+impl<SolutionA: Display, SolutionB: Display> Runnable for Day<SolutionA, SolutionB> {
+    fn run(&self, input: &str, parts: Parts) -> Result<Run> {
+        self.run_with_result(input, parts)
+    }
+
+    fn solve(&self, input: &str) -> Result<(String, String)> {
+        let solutions = self.run_with_result(input, Parts::Both)?.solutions;
+        Ok((solutions.a.unwrap(), solutions.b.unwrap()))
+    }
+}
+
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Runs `func` behind a panic boundary, turning a panic into an error with the panic message and
+/// location. Panics caught this way aren't printed by the panic hook.
+///
+/// ```
+/// use aoc_common::runner::catch_panic;
+///
+/// let error = catch_panic(|| -> anyhow::Result<()> { panic!("Unexpected char: ?") });
+/// assert!(error.unwrap_err().to_string().ends_with(": Unexpected char: ?"));
+/// assert_eq!(catch_panic(|| Ok(3)).unwrap(), 3);
+/// ```
+pub fn catch_panic<T>(func: impl FnOnce() -> Result<T>) -> Result<T> {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING_PANICS.get() {
+                let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+                LAST_PANIC.set(Some(match info.location() {
+                    Some(location) => format!("Panicked at {location}: {message}"),
+                    None => format!("Panicked: {message}"),
+                }));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING_PANICS.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING_PANICS.set(was_catching);
+
+    result.unwrap_or_else(|_| {
+        Err(anyhow!(
+            LAST_PANIC.take().unwrap_or_else(|| "Panicked".to_owned())
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::{Day, DynDay, PartAnswer, Parts, Runnable, catch_panic, phase};
+
+    #[test]
+    fn panic_becomes_error() {
+        let error =
+            catch_panic(|| -> Result<()> { panic!("Unexpected char: {}", '?') }).unwrap_err();
+        assert!(error.to_string().starts_with("Panicked at "));
+        assert!(error.to_string().contains("src/runner.rs:"));
+        assert!(error.to_string().ends_with(": Unexpected char: ?"));
+
+        assert_eq!(catch_panic(|| Ok(3)).unwrap(), 3);
+    }
+
+    #[test]
+    fn combined_phases() {
+        let day: Day<usize, usize> = Day::Combined(|input| {
+            phase("Parse");
+            let len = input.len();
+            phase("Part a");
+            phase("Part b");
+            Ok((len, len * 2))
+        });
+
+        let run = day.run("abc", Parts::Both).unwrap();
+        let names: Vec<_> = run.timings.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Combined parts", "Parse", "Part a", "Part b"]);
+        let phases = run.timings[1..].iter().map(|t| t.duration).sum();
+        assert_eq!(run.timings[0].duration, phases);
+        assert_eq!(run.solutions.a.as_deref(), Some("3"));
+        assert_eq!(run.solutions.b.as_deref(), Some("6"));
+    }
+
+    #[test]
+    fn mixed_answer_types() {
+        let days: Vec<Box<DynDay>> = vec![
+            Day::Combined(|input| Ok((input.len(), input.to_uppercase()))).into(),
+            Day::Separated(
+                |input| Ok(input.len() as i64),
+                |input| Ok(input.contains('b')),
+            )
+            .into(),
+            Day::BoolSeparated(|input, part_b| {
+                Ok(if part_b {
+                    PartAnswer::B(input.chars().rev().collect::<String>())
+                } else {
+                    PartAnswer::A(input.len() as u8)
+                })
+            })
+            .into(),
+        ];
+
+        let solutions: Vec<_> = days.iter().map(|day| day.solve("abc").unwrap()).collect();
+        assert_eq!(
+            solutions,
+            [
+                ("3".to_owned(), "ABC".to_owned()),
+                ("3".to_owned(), "true".to_owned()),
+                ("3".to_owned(), "cba".to_owned()),
+            ]
+        );
+    }
+}
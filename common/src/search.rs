@@ -0,0 +1,486 @@
+//! Shortest path searches over grids and arbitrary graphs.
+//!
+//! A graph is a closure that lists the successors of a node, with the cost of the step for the
+//! weighted searches. [`passable_neighbours`] builds one for an [`InfiniteGrid`]. The searches
+//! return a [`Search`] with the cost of every node they reached and, for each of them, the
+//! predecessors on all of its shortest paths.
+//!
+//! ```
+//! use aoc_common::search::{self, passable_neighbours};
+//! use aoc_common::{InfiniteGrid, SignedCoordinate};
+//!
+//! // Including empty cells in the extents keeps the whole input passable
+//! let maze = InfiniteGrid::<SignedCoordinate, char, true, true>::read("..#\n#..\n...".lines())?;
+//! let goal = SignedCoordinate::new(2, 2);
+//! let open = passable_neighbours(&maze, false, |cell| cell.is_none());
+//! let search = search::bfs(SignedCoordinate::ZERO, open, |&coord| coord == goal);
+//! assert_eq!(search.cost(), Some(4));
+//! assert_eq!(search.path().unwrap()[1], SignedCoordinate::new(1, 0));
+//! // Both ways around the bottom right corner are shortest
+//! assert_eq!(search.predecessors(&goal).len(), 2);
+//! # Ok::<(), aoc_common::ParseError>(())
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::coord::SignedCoordinate;
+use crate::grid::{CharConvertable, InfiniteGrid};
+
+#[derive(Clone, Debug)]
+struct Visit<Node, Cost> {
+    cost: Cost,
+    predecessors: Vec<Node>,
+}
+
+/// The result of a search: the goal if one was found, and for every node that was reached the
+/// cheapest known cost and the predecessors on the paths with that cost.
+///
+/// Costs are final for nodes that are at most as expensive as the goal. The search stops once it
+/// gets past the goal, so nodes it reached but didn't get to expand may have a cost that is too
+/// high.
+#[derive(Clone, Debug)]
+pub struct Search<Node, Cost> {
+    visits: FxHashMap<Node, Visit<Node, Cost>>,
+    goal: Option<Node>,
+}
+
+impl<Node: Clone + Eq + Hash, Cost: Copy + Ord> Search<Node, Cost> {
+    fn new(start: Node, cost: Cost) -> Self {
+        let visit = Visit {
+            cost,
+            predecessors: Vec::new(),
+        };
+        Self {
+            visits: FxHashMap::from_iter([(start, visit)]),
+            goal: None,
+        }
+    }
+
+    /// Records a step from `from` that reaches `node` for `cost`. Returns `node` if this is the
+    /// cheapest way to reach it so far, so it needs to be expanded (again).
+    fn reach(&mut self, from: &Node, node: Node, cost: Cost) -> Option<Node> {
+        match self.visits.entry(node) {
+            Entry::Vacant(entry) => {
+                let node = entry.key().clone();
+                entry.insert(Visit {
+                    cost,
+                    predecessors: vec![from.clone()],
+                });
+                Some(node)
+            }
+            Entry::Occupied(mut entry) => {
+                let visit = entry.get_mut();
+                match cost.cmp(&visit.cost) {
+                    Ordering::Less => {
+                        visit.cost = cost;
+                        visit.predecessors = vec![from.clone()];
+                        Some(entry.key().clone())
+                    }
+                    Ordering::Equal if !visit.predecessors.contains(from) => {
+                        visit.predecessors.push(from.clone());
+                        None
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// The goal that was found, if any.
+    pub fn goal(&self) -> Option<&Node> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of the cheapest path to the goal, if it was found.
+    pub fn cost(&self) -> Option<Cost> {
+        self.cost_to(self.goal.as_ref()?)
+    }
+
+    /// The cost of the cheapest known path to `node`, if it was reached.
+    pub fn cost_to(&self, node: &Node) -> Option<Cost> {
+        Some(self.visits.get(node)?.cost)
+    }
+
+    /// One of the cheapest paths to the goal, from the start to the goal, if it was found.
+    pub fn path(&self) -> Option<Vec<Node>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// One of the cheapest known paths to `node`, from the start to `node`, if it was reached.
+    pub fn path_to(&self, node: &Node) -> Option<Vec<Node>> {
+        let mut path = vec![node.clone()];
+        let mut visit = self.visits.get(node)?;
+        while let Some(previous) = visit.predecessors.first() {
+            path.push(previous.clone());
+            visit = &self.visits[previous];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The nodes right before `node` on each of the cheapest known paths to it. This is empty for
+    /// the start and for nodes that weren't reached.
+    pub fn predecessors(&self, node: &Node) -> &[Node] {
+        self.visits
+            .get(node)
+            .map_or(&[], |visit| visit.predecessors.as_slice())
+    }
+
+    /// Every node on any of the cheapest known paths to `node`, including the start and `node`.
+    /// This is empty if `node` wasn't reached.
+    pub fn on_cheapest_paths(&self, node: &Node) -> FxHashSet<Node> {
+        let mut nodes = FxHashSet::default();
+        if !self.visits.contains_key(node) {
+            return nodes;
+        }
+        let mut todo = vec![node.clone()];
+        while let Some(node) = todo.pop() {
+            todo.extend(
+                self.predecessors(&node)
+                    .iter()
+                    .filter(|previous| !nodes.contains(*previous))
+                    .cloned(),
+            );
+            nodes.insert(node);
+        }
+        nodes
+    }
+
+    /// Every node that was reached with its cheapest known cost, in no particular order.
+    pub fn reached(&self) -> impl Iterator<Item = (&Node, Cost)> {
+        self.visits.iter().map(|(node, visit)| (node, visit.cost))
+    }
+}
+
+/// Breadth first search from `start`, where every step costs 1, until a node for which
+/// `is_goal` holds. Pass `|_| false` as `is_goal` to get the distance to every reachable node.
+pub fn bfs<Node, Successors>(
+    start: Node,
+    mut successors: impl FnMut(&Node) -> Successors,
+    mut is_goal: impl FnMut(&Node) -> bool,
+) -> Search<Node, usize>
+where
+    Node: Clone + Eq + Hash,
+    Successors: IntoIterator<Item = Node>,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let cost = search.visits[&node].cost;
+        // Every predecessor of the goal is one step closer, so its layer is done
+        if search.cost().is_some_and(|goal| cost >= goal) {
+            break;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            continue;
+        }
+        for next in successors(&node) {
+            queue.extend(search.reach(&node, next, cost + 1));
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm from `start` until a node for which `is_goal` holds. `successors` lists
+/// the neighbours of a node with the cost of stepping to them, which must not be negative.
+pub fn dijkstra<Node, Cost, Successors>(
+    start: Node,
+    successors: impl FnMut(&Node) -> Successors,
+    is_goal: impl FnMut(&Node) -> bool,
+) -> Search<Node, Cost>
+where
+    Node: Clone + Eq + Hash,
+    Cost: Copy + Ord + Add<Output = Cost> + Default,
+    Successors: IntoIterator<Item = (Node, Cost)>,
+{
+    a_star(start, successors, |_| Cost::default(), is_goal)
+}
+
+/// A node waiting in the queue of [`a_star`], ordered so the max-heap pops the lowest estimate
+/// first, and the one that is furthest along of those.
+struct Queued<Node, Cost> {
+    estimate: Cost,
+    cost: Cost,
+    node: Node,
+}
+
+impl<Node, Cost: Ord> Ord for Queued<Node, Cost> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl<Node, Cost: Ord> PartialOrd for Queued<Node, Cost> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Node, Cost: Ord> PartialEq for Queued<Node, Cost> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Node, Cost: Ord> Eq for Queued<Node, Cost> {}
+
+/// A* search from `start` until a node for which `is_goal` holds, like [`dijkstra`] but guided by
+/// `heuristic`, which estimates the cost from a node to the goal. The estimate must never be more
+/// than the cost of a single step plus the estimate after it, and 0 at the goal, like
+/// [`SignedCoordinate::manhattan`] on a grid with steps of cost 1.
+pub fn a_star<Node, Cost, Successors>(
+    start: Node,
+    mut successors: impl FnMut(&Node) -> Successors,
+    mut heuristic: impl FnMut(&Node) -> Cost,
+    mut is_goal: impl FnMut(&Node) -> bool,
+) -> Search<Node, Cost>
+where
+    Node: Clone + Eq + Hash,
+    Cost: Copy + Ord + Add<Output = Cost> + Default,
+    Successors: IntoIterator<Item = (Node, Cost)>,
+{
+    let mut search = Search::new(start.clone(), Cost::default());
+    let mut queue = BinaryHeap::from([Queued {
+        estimate: heuristic(&start),
+        cost: Cost::default(),
+        node: start,
+    }]);
+    while let Some(Queued {
+        estimate,
+        cost,
+        node,
+    }) = queue.pop()
+    {
+        // Steps that cost nothing can still add predecessors to the goal until the queue gets
+        // past it
+        if search.cost().is_some_and(|goal| estimate > goal) {
+            break;
+        }
+        if cost > search.visits[&node].cost {
+            continue;
+        }
+        if search.goal.is_none() && is_goal(&node) {
+            search.goal = Some(node);
+            continue;
+        }
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+            if let Some(next) = search.reach(&node, next, cost) {
+                queue.push(Queued {
+                    estimate: cost + heuristic(&next),
+                    cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    search
+}
+
+/// Breadth first search from both `start` and `goal` until they meet, which explores far fewer
+/// nodes on large graphs. `predecessors` lists the nodes that have a step to a node, which is the
+/// same as `successors` on undirected graphs. Returns a shortest path from `start` to `goal`, its
+/// distance is one less than its length.
+pub fn bidirectional_bfs<Node, Successors, Predecessors>(
+    start: Node,
+    goal: Node,
+    mut successors: impl FnMut(&Node) -> Successors,
+    mut predecessors: impl FnMut(&Node) -> Predecessors,
+) -> Option<Vec<Node>>
+where
+    Node: Clone + Eq + Hash,
+    Successors: IntoIterator<Item = Node>,
+    Predecessors: IntoIterator<Item = Node>,
+{
+    if start == goal {
+        return Some(vec![start]);
+    }
+
+    // The node each node was reached from, towards the start and towards the goal
+    let mut forward = FxHashMap::from_iter([(start.clone(), None)]);
+    let mut backward = FxHashMap::from_iter([(goal.clone(), None)]);
+    let mut forward_layer = vec![start];
+    let mut backward_layer = vec![goal];
+    let meeting = loop {
+        if forward_layer.is_empty() || backward_layer.is_empty() {
+            return None;
+        }
+        // Grow the side with the smaller frontier
+        let meeting = if forward_layer.len() <= backward_layer.len() {
+            expand_layer(&mut forward_layer, &mut forward, &backward, &mut successors)
+        } else {
+            expand_layer(
+                &mut backward_layer,
+                &mut backward,
+                &forward,
+                &mut predecessors,
+            )
+        };
+        if let Some(meeting) = meeting {
+            break meeting;
+        }
+    };
+
+    let mut path = vec![meeting.clone()];
+    let mut node = &meeting;
+    while let Some(previous) = &forward[node] {
+        path.push(previous.clone());
+        node = previous;
+    }
+    path.reverse();
+    let mut node = &meeting;
+    while let Some(next) = &backward[node] {
+        path.push(next.clone());
+        node = next;
+    }
+    Some(path)
+}
+
+/// Replaces `layer` with the unvisited neighbours of its nodes. Returns the first of them that the
+/// other side has visited already.
+fn expand_layer<Node: Clone + Eq + Hash, Neighbours: IntoIterator<Item = Node>>(
+    layer: &mut Vec<Node>,
+    parents: &mut FxHashMap<Node, Option<Node>>,
+    other: &FxHashMap<Node, Option<Node>>,
+    neighbours: &mut impl FnMut(&Node) -> Neighbours,
+) -> Option<Node> {
+    for node in std::mem::take(layer) {
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next) {
+                let next = entry.key().clone();
+                entry.insert(Some(node.clone()));
+                if other.contains_key(&next) {
+                    return Some(next);
+                }
+                layer.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// The successors of a cell of `grid` for the searches: its neighbours inside the extents of the
+/// grid for which `passable` holds, where `passable` gets `None` for empty cells.
+/// Empty cells only widen the extents of grids that include them, so read grids with empty
+/// passable cells with `INCLUDE_EMPTY`.
+pub fn passable_neighbours<
+    Data: Clone + CharConvertable + PartialEq,
+    const CACHED_EXTENTS: bool,
+    const INCLUDE_EMPTY: bool,
+>(
+    grid: &InfiniteGrid<SignedCoordinate, Data, CACHED_EXTENTS, INCLUDE_EMPTY>,
+    include_diagonal: bool,
+    passable: impl Fn(Option<&Data>) -> bool,
+) -> impl Fn(&SignedCoordinate) -> Vec<SignedCoordinate> {
+    let extents = grid.checked_extents();
+    move |coord| {
+        let Some((min, max)) = extents else {
+            return Vec::new();
+        };
+        let neighbours: Vec<_> = if include_diagonal {
+            coord.neighbours::<true>().collect()
+        } else {
+            coord.neighbours::<false>().collect()
+        };
+        neighbours
+            .into_iter()
+            .filter(|n| {
+                (min.x..=max.x).contains(&n.x)
+                    && (min.y..=max.y).contains(&n.y)
+                    && passable(grid.get(n))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{a_star, bfs, bidirectional_bfs, dijkstra, passable_neighbours};
+    use crate::{InfiniteGrid, SignedCoordinate};
+
+    const MAZE: &str = "\
+S......#
+.####..#
+.#..#.##
+.#.##...
+...#.#.E";
+
+    #[test]
+    fn grid_searches_agree() {
+        let maze = InfiniteGrid::<SignedCoordinate, char, true>::read(MAZE.lines()).unwrap();
+        let open = passable_neighbours(&maze, false, |cell| cell != Some(&'#'));
+        let weighted = |coord: &SignedCoordinate| open(coord).into_iter().map(|n| (n, 1));
+        let (start, goal) = (SignedCoordinate::ZERO, SignedCoordinate::new(7, 4));
+
+        let breadth_first = bfs(start, &open, |&coord| coord == goal);
+        assert_eq!(breadth_first.cost(), Some(11));
+        assert_eq!(dijkstra(start, weighted, |&c| c == goal).cost(), Some(11));
+        let guided = a_star(start, weighted, |c| c.manhattan(&goal), |&c| c == goal);
+        assert_eq!(guided.cost(), Some(11));
+        // The heuristic keeps A* out of the dead end on the left
+        assert_eq!(guided.cost_to(&SignedCoordinate::new(2, 2)), None);
+
+        for path in [
+            breadth_first.path().unwrap(),
+            guided.path().unwrap(),
+            bidirectional_bfs(start, goal, &open, &open).unwrap(),
+        ] {
+            assert_eq!(path.len(), 12);
+            assert_eq!((path[0], path[11]), (start, goal));
+            assert!(
+                path.windows(2)
+                    .all(|step| open(&step[0]).contains(&step[1]))
+            );
+        }
+
+        let walled = SignedCoordinate::new(4, 4);
+        assert_eq!(bfs(start, &open, |&c| c == walled).cost(), None);
+        assert_eq!(bidirectional_bfs(goal, walled, &open, &open), None);
+        assert_eq!(
+            bidirectional_bfs(goal, goal, &open, &open),
+            Some(vec![goal])
+        );
+    }
+
+    #[test]
+    fn cheapest_paths() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 3, 0 -> 3 costs 4 and 3 -> 4 is free
+        let edges = [
+            (0, 1, 1),
+            (1, 3, 2),
+            (0, 2, 2),
+            (2, 3, 1),
+            (0, 3, 4),
+            (3, 4, 0),
+        ];
+        let successors = |&node: &u32| {
+            edges
+                .iter()
+                .filter(move |(from, _, _)| *from == node)
+                .map(|&(_, to, cost)| (to, cost))
+        };
+        let search = dijkstra(0, successors, |&node| node == 4);
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.predecessors(&3), [1, 2]);
+        assert_eq!(search.predecessors(&0), []);
+        let mut on_paths: Vec<_> = search.on_cheapest_paths(&4).into_iter().collect();
+        on_paths.sort_unstable();
+        assert_eq!(on_paths, [0, 1, 2, 3, 4]);
+
+        let everything = bfs(0, |node| successors(node).map(|(to, _)| to), |_| false);
+        assert_eq!(everything.goal(), None);
+        assert_eq!(everything.cost_to(&3), Some(1));
+        assert_eq!(everything.path_to(&4), Some(vec![0, 3, 4]));
+        assert_eq!(everything.reached().count(), 5);
+    }
+}
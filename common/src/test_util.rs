@@ -0,0 +1,19 @@
+//! Helpers shared by the unit tests of the crate.
+
+/// A small xorshift generator, so randomised tests are reproducible without extra crates.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    /// A generator starting from `seed`, which must not be 0.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// The next pseudo-random number.
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use aoc_common::Solutions;
+
+pub const ANSWERS_ENV: &str = "AOC_ANSWERS";
+pub const DEFAULT_ANSWERS: &str = "answers";
+
+/// Known-correct answers, stored as `<dir>/<year>/<day>.txt` with part a on the first line and
+/// part b on the second. A missing file or an empty line means the answer isn't known yet.
+#[derive(Debug)]
+pub struct Answers {
+    dir: PathBuf,
+}
+
+impl Answers {
+    /// Uses `dir` if given, otherwise falls back to `$AOC_ANSWERS` and then to `answers`.
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        let dir = dir
+            .or_else(|| std::env::var_os(ANSWERS_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS));
+        Self { dir }
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("{day}.txt"))
+    }
+
+    pub fn load(&self, year: u32, day: u32) -> Result<Solutions> {
+        let path = self.path(year, day);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Solutions::default()),
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Could not read answers for day {day} at {}", path.display())
+                });
+            }
+        };
+
+        let mut lines = contents
+            .lines()
+            .map(str::trim)
+            .map(|line| (!line.is_empty()).then(|| line.to_owned()));
+        Ok(Solutions {
+            a: lines.next().flatten(),
+            b: lines.next().flatten(),
+        })
+    }
+}
@@ -0,0 +1,297 @@
+use std::fmt::Write;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow};
+use aoc_common::Parts;
+use rustc_hash::FxHashMap;
+use tracing::{error, info, warn};
+
+use crate::input::Inputs;
+use crate::registry::Entry;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Budget {
+    Runs(usize),
+    Time(Duration),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub budget: Budget,
+    /// Baseline to compare the medians against.
+    pub baseline: Option<PathBuf>,
+    /// Where to store the medians of this run as the new baseline.
+    pub save_baseline: Option<PathBuf>,
+    /// How much slower than the baseline, in percent, a day may get before it counts as a
+    /// regression.
+    pub threshold: f64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            budget: Budget::Runs(10),
+            baseline: None,
+            save_baseline: None,
+            threshold: 5.0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+
+        let runs = samples.len();
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Median solve times per year and day, stored as `<year> <day> <median in ns>` lines.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: FxHashMap<(u32, u32), Duration>,
+}
+
+impl Baseline {
+    /// Loads the baseline at `path`, which has to exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::read(path)?.ok_or_else(|| anyhow!("No baseline at {}", path.display()))
+    }
+
+    /// Loads the baseline at `path`, or an empty one if there is no file yet.
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        Ok(Self::read(path)?.unwrap_or_default())
+    }
+
+    fn read(path: &Path) -> Result<Option<Self>> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Could not read baseline at {}", path.display()));
+            }
+        };
+
+        let mut medians = FxHashMap::default();
+        for line in contents.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let [year, day, nanos] = line
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| {
+                    anyhow!("Invalid baseline line: {line}, expected <year> <day> <median in ns>")
+                })?;
+            medians.insert(
+                (year.parse()?, day.parse()?),
+                Duration::from_nanos(nanos.parse()?),
+            );
+        }
+        Ok(Some(Self { medians }))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut days: Vec<_> = self.medians.iter().collect();
+        days.sort_unstable();
+        let mut contents = String::new();
+        for ((year, day), median) in days {
+            writeln!(contents, "{year} {day} {}", median.as_nanos())?;
+        }
+        std::fs::write(path, contents)
+            .with_context(|| format!("Could not write baseline to {}", path.display()))
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<Duration> {
+        self.medians.get(&(year, day)).copied()
+    }
+
+    pub fn set(&mut self, year: u32, day: u32, median: Duration) {
+        self.medians.insert((year, day), median);
+    }
+}
+
+/// Runs a single day repeatedly on an input that was loaded up front, so only solving is timed.
+pub fn bench_day(entry: &Entry, input: &str, parts: Parts, config: &BenchConfig) -> Result<Stats> {
+    for _ in 0..config.warmup {
+        entry.run(input, parts)?;
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    loop {
+        let now = Instant::now();
+        entry.run(input, parts)?;
+        samples.push(now.elapsed());
+
+        let done = match config.budget {
+            Budget::Runs(runs) => samples.len() >= runs,
+            Budget::Time(budget) => start.elapsed() >= budget,
+        };
+        if done {
+            break;
+        }
+    }
+    Ok(Stats::from_samples(&mut samples))
+}
+
+/// Benchmarks all given days, returning whether they all ran without errors or regressions.
+pub fn run(entries: &[&Entry], inputs: &Inputs, parts: Parts, config: &BenchConfig) -> bool {
+    let baseline = match config.baseline.as_deref().map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            error!("{e:#}");
+            return false;
+        }
+    };
+    let mut new_baseline = match config
+        .save_baseline
+        .as_deref()
+        .map(Baseline::load_or_default)
+    {
+        Some(Ok(baseline)) => baseline,
+        Some(Err(e)) => {
+            error!("{e:#}");
+            return false;
+        }
+        None => Baseline::default(),
+    };
+
+    let mut success = true;
+    for entry in entries {
+        let stats = inputs
+            .load(entry.year, entry.day)
+            .and_then(|input| bench_day(entry, &input, parts, config));
+        let stats = match stats {
+            Ok(stats) => stats,
+            Err(e) => {
+                error!("Error occurred benchmarking day {}: {e:#}", entry.day);
+                success = false;
+                continue;
+            }
+        };
+
+        info!(
+            "Day {}: min {:#?}, median {:#?}, mean {:#?}, stddev {:#?} over {} runs",
+            entry.day, stats.min, stats.median, stats.mean, stats.stddev, stats.runs
+        );
+
+        let previous = baseline.as_ref().and_then(|b| b.get(entry.year, entry.day));
+        if previous.is_some_and(|previous| previous.is_zero()) {
+            warn!(
+                "Day {}: baseline median is zero, not comparing against it",
+                entry.day
+            );
+        } else if let Some(previous) = previous {
+            let change = (stats.median.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0;
+            if change > config.threshold {
+                warn!(
+                    "Day {}: median regressed by {change:+.1}% ({:#?} -> {:#?})",
+                    entry.day, previous, stats.median
+                );
+                success = false;
+            } else {
+                info!(
+                    "Day {}: median changed by {change:+.1}% ({:#?} -> {:#?})",
+                    entry.day, previous, stats.median
+                );
+            }
+        }
+        new_baseline.set(entry.year, entry.day, stats.median);
+    }
+
+    if let Some(path) = &config.save_baseline
+        && let Err(e) = new_baseline.save(path)
+    {
+        error!("{e:#}");
+        success = false;
+    }
+    success
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Baseline, Stats};
+    use crate::harness::temp_dir;
+
+    #[test]
+    fn stats() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn baselines_keep_years_apart() {
+        let dir = temp_dir("baseline");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("baseline.txt");
+
+        let mut baseline = Baseline::default();
+        baseline.set(2025, 1, Duration::from_millis(2));
+        baseline.set(2024, 1, Duration::from_millis(1));
+        baseline.save(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "2024 1 1000000\n2025 1 2000000\n"
+        );
+
+        let baseline = Baseline::load(&path).unwrap();
+        assert_eq!(baseline.get(2025, 1), Some(Duration::from_millis(2)));
+        assert_eq!(baseline.get(2024, 1), Some(Duration::from_millis(1)));
+        assert_eq!(baseline.get(2023, 1), None);
+
+        // A missing baseline is only fine when it is about to be created
+        let missing = dir.join("missing.txt");
+        assert_eq!(
+            Baseline::load(&missing).unwrap_err().to_string(),
+            format!("No baseline at {}", missing.display())
+        );
+        assert_eq!(
+            Baseline::load_or_default(&missing).unwrap().get(2025, 1),
+            None
+        );
+
+        // Baselines from before years were tracked can't be told apart
+        std::fs::write(&path, "1 1000000\n").unwrap();
+        assert!(Baseline::load(&path).is_err());
+    }
+}
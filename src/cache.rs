@@ -0,0 +1,233 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use aoc_common::net::{self, HttpClient, Method, Request, Throttle};
+use tracing::{error, info};
+
+pub const CACHE_ENV: &str = "AOC_CACHE";
+pub const DEFAULT_CACHE: &str = ".cache";
+pub const DEFAULT_THROTTLE: Duration = Duration::from_secs(5);
+/// File in the cache directory holding the time of the last request to the site.
+pub const THROTTLE_FILE: &str = ".last_request";
+
+/// Uses `dir` if given, otherwise falls back to `$AOC_CACHE` and then to `.cache`.
+pub fn resolve_dir(dir: Option<PathBuf>) -> PathBuf {
+    dir.or_else(|| std::env::var_os(CACHE_ENV).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE))
+}
+
+/// Puzzle inputs downloaded from the site, stored as `<dir>/<year>/<day>.txt`. An input that is
+/// in the cache is never downloaded again.
+pub struct InputCache {
+    dir: PathBuf,
+    base_url: String,
+    client: Box<dyn HttpClient>,
+    session: Option<String>,
+    throttle: Throttle,
+}
+
+impl InputCache {
+    /// The time of the last request is kept in the cache as well, so the throttle holds across
+    /// runs.
+    pub fn new(
+        dir: PathBuf,
+        base_url: String,
+        client: Box<dyn HttpClient>,
+        throttle: Duration,
+    ) -> Self {
+        let throttle = Throttle::new(throttle, Some(dir.join(THROTTLE_FILE)));
+        Self {
+            dir,
+            base_url,
+            client,
+            session: None,
+            throttle,
+        }
+    }
+
+    /// Uses the given session token instead of looking it up with `net::load_session`.
+    #[cfg(test)]
+    pub fn with_session(mut self, session: String) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("{day}.txt"))
+    }
+
+    pub fn cached(&self, year: u32, day: u32) -> Result<Option<String>> {
+        let path = self.path(year, day);
+        match std::fs::read_to_string(&path) {
+            Ok(input) => Ok(Some(input)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Could not read {}", path.display())),
+        }
+    }
+
+    /// Returns the cached input, downloading and storing it first if it isn't cached yet.
+    pub fn get(&self, year: u32, day: u32) -> Result<String> {
+        if let Some(input) = self.cached(year, day)? {
+            return Ok(input);
+        }
+
+        let session = match &self.session {
+            Some(session) => session.clone(),
+            None => net::load_session()?,
+        };
+        let request = Request {
+            method: Method::Get,
+            url: format!("{}/{year}/day/{day}/input", self.base_url),
+            session,
+            body: None,
+        };
+        let response = self.throttle.run(|| self.client.send(&request))?;
+        if response.status != 200 {
+            bail!(
+                "Could not fetch input for day {day} of {year}: HTTP {} {}",
+                response.status,
+                response.body.trim()
+            );
+        }
+
+        let path = self.path(year, day);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        // Write to a temporary file first so an interrupted download never looks cached
+        let partial = path.with_extension("txt.partial");
+        std::fs::write(&partial, &response.body)?;
+        std::fs::rename(&partial, &path)
+            .with_context(|| format!("Could not store input at {}", path.display()))?;
+        Ok(response.body)
+    }
+
+    /// Makes sure all given days are cached, returning whether that succeeded for all of them.
+    pub fn fetch_all(&self, year: u32, days: &[u32]) -> bool {
+        let mut success = true;
+        for &day in days {
+            let was_cached = self.path(year, day).exists();
+            match self.get(year, day) {
+                Ok(_) if was_cached => info!(
+                    "Input for day {day} already cached at {}",
+                    self.path(year, day).display()
+                ),
+                Ok(_) => info!(
+                    "Fetched input for day {day} to {}",
+                    self.path(year, day).display()
+                ),
+                Err(e) => {
+                    error!("{e:#}");
+                    success = false;
+                }
+            }
+        }
+        success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    use aoc_common::net::PlainHttpClient;
+    use aoc_common::net::stub::{Received, StubServer};
+
+    use super::InputCache;
+    use crate::harness;
+
+    /// A cache in a fresh directory, fetching from a stub that answers with `respond`.
+    fn cache(
+        name: &str,
+        respond: impl Fn(&Received) -> (u16, String) + Send + 'static,
+        client: PlainHttpClient,
+        throttle: Duration,
+    ) -> (StubServer, PathBuf, InputCache) {
+        let (server, dir, cache) =
+            harness::stub_site(name, respond, InputCache::new, client, throttle);
+        (server, dir, cache.with_session("secret".to_owned()))
+    }
+
+    #[test]
+    fn fetches_once() {
+        let (server, dir, cache) = cache(
+            "fetches_once",
+            |request| (200, format!("input for {}\n", request.path)),
+            PlainHttpClient::default(),
+            Duration::ZERO,
+        );
+
+        assert_eq!(cache.cached(2025, 3).unwrap(), None);
+        assert_eq!(cache.get(2025, 3).unwrap(), "input for /2025/day/3/input\n");
+        assert_eq!(cache.get(2025, 3).unwrap(), "input for /2025/day/3/input\n");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("2025/3.txt")).unwrap(),
+            "input for /2025/day/3/input\n"
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert!(requests[0].body.is_empty());
+        assert!(
+            requests[0]
+                .headers
+                .contains(&"Cookie: session=secret".to_owned())
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_fetch_is_not_cached() {
+        let (_server, dir, cache) = cache(
+            "failed_fetch",
+            |_| (404, "Not Found".to_owned()),
+            PlainHttpClient::default(),
+            Duration::ZERO,
+        );
+
+        let error = cache.get(2025, 30).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not fetch input for day 30 of 2025: HTTP 404 Not Found"
+        );
+        assert_eq!(cache.cached(2025, 30).unwrap(), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn throttles_requests() {
+        let (server, dir, cache) = cache(
+            "throttles",
+            |_| (200, "input".to_owned()),
+            PlainHttpClient::default(),
+            Duration::from_millis(200),
+        );
+
+        let start = Instant::now();
+        cache.get(2025, 1).unwrap();
+        cache.get(2025, 2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(server.requests().len(), 2);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sends_user_agent() {
+        let (server, dir, cache) = cache(
+            "user_agent",
+            |_| (200, "input".to_owned()),
+            PlainHttpClient::new("example.com/aoc by someone"),
+            Duration::ZERO,
+        );
+
+        cache.get(2025, 1).unwrap();
+        assert!(
+            server.requests()[0]
+                .headers
+                .contains(&"User-Agent: example.com/aoc by someone".to_owned())
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,553 @@
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow, bail};
+use aoc_common::{Part, Parts};
+use rustc_hash::FxHashMap;
+
+use crate::bench::{BenchConfig, Budget};
+use crate::cache::DEFAULT_THROTTLE;
+use crate::input::Source;
+use crate::report::Format;
+use crate::scaffold::{NewDay, Template};
+use crate::submit::Submission;
+
+pub const USAGE: &str = "\
+Usage: aoc [DAYS] [--year YEAR] [--part a|b|both] [--inputs DIR] [--input DAY=PATH]...
+           [--stdin] [--answers DIR] [--format text|json] [--parallel | --jobs N]
+           [--timeout SECONDS] [--bench [BENCH OPTIONS]] [CACHE OPTIONS]
+       aoc fetch [DAYS] [--year YEAR] [CACHE OPTIONS]
+       aoc submit DAY PART [ANSWER] [--year YEAR] [--inputs DIR] [--input DAY=PATH] [CACHE OPTIONS]
+       aoc new DAY [--year YEAR] [--grid] [--title TITLE] [--answers DIR]
+
+The fetch command downloads the inputs for the given days into the input cache.
+
+The submit command posts ANSWER for PART (a or b) of DAY, or the answer the solver finds if ANSWER
+is left out. Every submission is recorded in the cache, answers that are already known to be wrong
+or that lie outside the too high and too low bounds are refused.
+
+The new command creates src/y<YEAR>/day<DAY>.rs from a template along with an empty answers file,
+and registers the day in src/y<YEAR>/mod.rs, creating and registering the year module if needed.
+It has to be run from the root of the repository and never overwrites existing files. --grid picks
+a template that reads the input into an InfiniteGrid, --title sets the puzzle title.
+
+Everything applies to a single year, which defaults to $AOC_YEAR or the latest implemented year.
+DAYS selects which days of it to run and defaults to all implemented days:
+  all          every implemented day
+  3            a single day
+  1-5          an inclusive range of days
+  1,3,7        a list of days, entries may be ranges (e.g. 1-3,6)
+
+Options:
+  -y, --year <YEAR>       the year to run
+  -p, --part <PART>       which part(s) to report: a, b or both (default both)
+  -i, --inputs <DIR>      directory containing <year>/<day>.txt inputs, defaults to $AOC_INPUTS or
+                          inputs
+      --input <DAY=PATH>  read the input for DAY from PATH instead, - reads from stdin
+      --stdin             read the input for the single selected day from stdin
+  -a, --answers <DIR>     directory containing known <year>/<day>.txt answers, defaults to
+                          $AOC_ANSWERS or answers
+  -f, --format <FORMAT>   text logs (default) or json, which prints one JSON record per day to
+                          stdout and moves the logs to stderr
+      --parallel          run days concurrently on all available cores, reports stay in day order
+  -j, --jobs <N>          run days concurrently on N threads
+  -t, --timeout <SECONDS> give up on a day that runs for longer than this and report it as
+                          timed out
+  -h, --help              print this help
+
+Benchmarking:
+      --bench                 run each day repeatedly and report timing statistics
+      --runs <N>              number of timed runs per day (default 10)
+      --time <SECONDS>        keep running each day for this long instead of a fixed number of runs
+      --warmup <N>            number of untimed warm-up runs per day (default 1)
+      --baseline <PATH>       compare the medians against a previously saved baseline
+      --save-baseline <PATH>  store the medians of this run as a baseline
+      --threshold <PERCENT>   slowdown against the baseline that counts as a regression (default 5)
+
+Input cache:
+      --fetch                 download inputs missing from the inputs directory into the cache
+  -c, --cache <DIR>           cache directory, defaults to $AOC_CACHE or .cache
+      --base-url <URL>        puzzle site to fetch from, defaults to $AOC_BASE_URL or
+                              https://adventofcode.com
+      --throttle <SECONDS>    minimum time between requests to the site (default 5)
+
+The session token is read from $AOC_SESSION, or from the file at $AOC_SESSION_FILE or .session.
+Requests identify themselves with the user agent in $AOC_USER_AGENT if it is set";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DaySelection {
+    All,
+    Days(Vec<u32>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    /// `None` picks the default year.
+    pub year: Option<u32>,
+    pub days: DaySelection,
+    pub parts: Parts,
+    pub inputs: Option<PathBuf>,
+    pub input_overrides: FxHashMap<u32, Source>,
+    pub stdin: bool,
+    pub answers: Option<PathBuf>,
+    pub format: Format,
+    /// Number of threads to run days on, `None` runs them one after another.
+    pub jobs: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub bench: Option<BenchConfig>,
+    pub fetch: bool,
+    pub cache: Option<PathBuf>,
+    pub base_url: Option<String>,
+    pub throttle: Duration,
+    pub action: Action,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Box<Args>),
+    Help,
+}
+
+/// What to do with the parsed arguments, picked by the subcommand.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Run the selected days, or benchmark them with `--bench`.
+    Run,
+    /// Download the inputs of the selected days into the cache.
+    Fetch,
+    Submit(Submission),
+    New(NewDay),
+}
+
+impl DaySelection {
+    /// The selected days without checking whether they are implemented, where `All` means all
+    /// implemented days.
+    pub fn days(&self, implemented: &[u32]) -> Vec<u32> {
+        match self {
+            DaySelection::All => implemented.to_vec(),
+            DaySelection::Days(days) => days.clone(),
+        }
+    }
+
+    /// Resolves the selection against the days that are actually implemented, failing on the
+    /// first day that is out of range or has no solver yet.
+    pub fn resolve(&self, implemented: &[u32]) -> Result<Vec<u32>> {
+        match self {
+            DaySelection::All => Ok(implemented.to_vec()),
+            DaySelection::Days(days) => days
+                .iter()
+                .map(|&day| {
+                    if implemented.contains(&day) {
+                        Ok(day)
+                    } else {
+                        Err(anyhow!("Day {day} is not implemented yet"))
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command> {
+    let mut year = None;
+    let mut days = None;
+    let mut parts = Parts::Both;
+    let mut inputs = None;
+    let mut input_overrides = FxHashMap::default();
+    let mut stdin = false;
+    let mut answers = None;
+    let mut format = Format::Text;
+    let mut jobs = None;
+    let mut timeout = None;
+    let mut bench = false;
+    let mut bench_config = BenchConfig::default();
+    let mut bench_options = Vec::new();
+    let mut fetch = false;
+    let mut cache = None;
+    let mut base_url = None;
+    let mut throttle = DEFAULT_THROTTLE;
+    let mut positionals = Vec::new();
+    let mut template = Template::Lines;
+    let mut title = None;
+
+    let mut args = args.into_iter().peekable();
+    let subcommand = args.next_if(|arg| matches!(arg.as_str(), "fetch" | "submit" | "new"));
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name, Some(value.to_owned())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| anyhow!("Missing value for {name}"))
+        };
+
+        match name {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-y" | "--year" => year = Some(parse_year(&value()?)?),
+            "-p" | "--part" => parts = parse_parts(&value()?)?,
+            "-i" | "--inputs" => inputs = Some(PathBuf::from(value()?)),
+            "--input" => {
+                let (day, source) = parse_input_override(&value()?)?;
+                input_overrides.insert(day, source);
+            }
+            "--stdin" => stdin = true,
+            "-a" | "--answers" => answers = Some(PathBuf::from(value()?)),
+            "-f" | "--format" => format = parse_format(&value()?)?,
+            "--parallel" => {
+                jobs = Some(thread::available_parallelism().map_or(1, NonZeroUsize::get));
+            }
+            "-j" | "--jobs" => jobs = Some(parse_jobs(&value()?)?),
+            "-t" | "--timeout" => timeout = Some(parse_seconds(name, &value()?)?),
+            "--bench" => bench = true,
+            "--runs" | "--time" | "--warmup" | "--baseline" | "--save-baseline" | "--threshold" => {
+                parse_bench_option(&mut bench_config, name, value()?)?;
+                bench_options.push(name.to_owned());
+            }
+            "--fetch" => fetch = true,
+            "-c" | "--cache" => cache = Some(PathBuf::from(value()?)),
+            "--base-url" => base_url = Some(value()?.trim_end_matches('/').to_owned()),
+            "--throttle" => throttle = parse_seconds(name, &value()?)?,
+            "--grid" if subcommand.as_deref() == Some("new") => template = Template::Grid,
+            "--title" if subcommand.as_deref() == Some("new") => title = Some(value()?),
+            _ if name.starts_with('-') => bail!("Unknown option: {arg}"),
+            _ if matches!(subcommand.as_deref(), Some("submit" | "new")) => positionals.push(arg),
+            _ => {
+                if days.is_some() {
+                    bail!("Days were specified more than once: {arg}");
+                }
+                days = Some(parse_days(&arg)?);
+            }
+        }
+    }
+
+    let stdin_overrides = input_overrides
+        .values()
+        .filter(|source| **source == Source::Stdin)
+        .count();
+    if stdin_overrides + usize::from(stdin) > 1 {
+        bail!("Only one day can read its input from stdin");
+    }
+
+    if !bench && let Some(option) = bench_options.first() {
+        bail!("{option} can only be used together with --bench");
+    }
+    if bench_config.budget == Budget::Runs(0) {
+        bail!("--runs must be at least 1");
+    }
+
+    let action = parse_action(subcommand.as_deref(), positionals, title, template)?;
+
+    Ok(Command::Run(Box::new(Args {
+        year,
+        days: days.unwrap_or(DaySelection::All),
+        parts,
+        inputs,
+        input_overrides,
+        stdin,
+        answers,
+        format,
+        jobs,
+        timeout,
+        bench: bench.then_some(bench_config),
+        fetch,
+        cache,
+        base_url,
+        throttle,
+        action,
+    })))
+}
+
+fn parse_action(
+    subcommand: Option<&str>,
+    positionals: Vec<String>,
+    title: Option<String>,
+    template: Template,
+) -> Result<Action> {
+    Ok(match subcommand {
+        Some("fetch") => Action::Fetch,
+        Some("submit") => Action::Submit(parse_submission(positionals)?),
+        Some("new") => {
+            let [day] = &positionals[..] else {
+                bail!("new needs exactly one day");
+            };
+            Action::New(NewDay {
+                day: parse_day(day)?,
+                title,
+                template,
+            })
+        }
+        _ => Action::Run,
+    })
+}
+
+fn parse_submission(positionals: Vec<String>) -> Result<Submission> {
+    let mut positionals = positionals.into_iter();
+    let (Some(day), Some(part)) = (positionals.next(), positionals.next()) else {
+        bail!("submit needs a day and a part");
+    };
+    let submission = Submission {
+        day: parse_day(&day)?,
+        part: match part.as_str() {
+            "a" | "A" | "1" => Part::A,
+            "b" | "B" | "2" => Part::B,
+            _ => bail!("Unknown part: {part} (expected a or b)"),
+        },
+        answer: positionals.next(),
+    };
+    if let Some(extra) = positionals.next() {
+        bail!("Unexpected argument: {extra}");
+    }
+    Ok(submission)
+}
+
+fn parse_jobs(value: &str) -> Result<usize> {
+    let jobs = parse_number("--jobs", value)?;
+    if jobs == 0 {
+        bail!("--jobs must be at least 1");
+    }
+    Ok(jobs)
+}
+
+fn parse_bench_option(config: &mut BenchConfig, name: &str, value: String) -> Result<()> {
+    match name {
+        "--runs" => config.budget = Budget::Runs(parse_number(name, &value)?),
+        "--time" => config.budget = Budget::Time(parse_seconds(name, &value)?),
+        "--warmup" => config.warmup = parse_number(name, &value)?,
+        "--baseline" => config.baseline = Some(PathBuf::from(value)),
+        "--save-baseline" => config.save_baseline = Some(PathBuf::from(value)),
+        _ => config.threshold = parse_number(name, &value)?,
+    }
+    Ok(())
+}
+
+fn parse_seconds(name: &str, value: &str) -> Result<Duration> {
+    Ok(Duration::try_from_secs_f64(parse_number(name, value)?)?)
+}
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("Invalid value for {name}: {value}"))
+}
+
+fn parse_format(value: &str) -> Result<Format> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => bail!("Unknown format: {value} (expected text or json)"),
+    }
+}
+
+fn parse_input_override(value: &str) -> Result<(u32, Source)> {
+    let (day, path) = value
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid input override: {value} (expected DAY=PATH)"))?;
+    Ok((parse_day(day)?, Source::parse(path)))
+}
+
+fn parse_parts(value: &str) -> Result<Parts> {
+    match value {
+        "a" | "A" => Ok(Parts::A),
+        "b" | "B" => Ok(Parts::B),
+        "both" | "ab" => Ok(Parts::Both),
+        _ => bail!("Unknown part: {value} (expected a, b or both)"),
+    }
+}
+
+fn parse_days(value: &str) -> Result<DaySelection> {
+    if value == "all" {
+        return Ok(DaySelection::All);
+    }
+
+    let mut days = Vec::new();
+    for entry in value.split(',') {
+        if let Some((start, end)) = entry.split_once('-') {
+            let start = parse_day(start)?;
+            let end = parse_day(end)?;
+            if start > end {
+                bail!("Invalid day range: {entry}");
+            }
+            days.extend(start..=end);
+        } else {
+            days.push(parse_day(entry)?);
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(DaySelection::Days(days))
+}
+
+fn parse_year(value: &str) -> Result<u32> {
+    let year: u32 = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid year: {value:?}"))?;
+    if year < 2015 {
+        bail!("Year {year} does not exist, the first year was 2015");
+    }
+    Ok(year)
+}
+
+fn parse_day(value: &str) -> Result<u32> {
+    let day: u32 = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid day: {value:?}"))?;
+    if !(1..=25).contains(&day) {
+        bail!("Day {day} does not exist, days range from 1 to 25");
+    }
+    Ok(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use anyhow::Result;
+    use aoc_common::{Part, Parts};
+
+    use super::{Action, Args, Command, DaySelection, parse, parse_days};
+    use crate::input::Source;
+    use crate::scaffold::{NewDay, Template};
+    use crate::submit::Submission;
+
+    fn args(line: &str) -> Result<Args> {
+        match parse(line.split_whitespace().map(str::to_owned))? {
+            Command::Run(args) => Ok(*args),
+            Command::Help => panic!("{line} asked for help"),
+        }
+    }
+
+    fn error(line: &str) -> String {
+        args(line).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn day_ranges() {
+        assert_eq!(parse_days("all").unwrap(), DaySelection::All);
+        assert_eq!(parse_days("3").unwrap(), DaySelection::Days(vec![3]));
+        assert_eq!(
+            parse_days("6,1-3,2").unwrap(),
+            DaySelection::Days(vec![1, 2, 3, 6])
+        );
+        assert_eq!(parse_days("4-4").unwrap(), DaySelection::Days(vec![4]));
+
+        let error = |value| parse_days(value).unwrap_err().to_string();
+        assert_eq!(error("5-3"), "Invalid day range: 5-3");
+        assert_eq!(error("1-"), "Invalid day: \"\"");
+        assert_eq!(error("1--3"), "Invalid day: \"-3\"");
+        assert_eq!(error("1,,2"), "Invalid day: \"\"");
+        assert_eq!(error("0"), "Day 0 does not exist, days range from 1 to 25");
+        assert_eq!(
+            error("20-26"),
+            "Day 26 does not exist, days range from 1 to 25"
+        );
+    }
+
+    #[test]
+    fn resolves_against_implemented_days() {
+        let implemented = [1, 2, 3];
+        assert_eq!(DaySelection::All.resolve(&implemented).unwrap(), [1, 2, 3]);
+        let selection = DaySelection::Days(vec![1, 3]);
+        assert_eq!(selection.resolve(&implemented).unwrap(), [1, 3]);
+
+        let selection = DaySelection::Days(vec![2, 4, 5]);
+        assert_eq!(
+            selection.resolve(&implemented).unwrap_err().to_string(),
+            "Day 4 is not implemented yet"
+        );
+        assert_eq!(selection.days(&implemented), [2, 4, 5]);
+    }
+
+    #[test]
+    fn options() {
+        let parsed = args("1-2 --part=b --input 1=in.txt --input=2=- -j 3 --year 2024").unwrap();
+        assert_eq!(parsed.days, DaySelection::Days(vec![1, 2]));
+        assert_eq!(parsed.parts, Parts::B);
+        assert_eq!(
+            parsed.input_overrides.get(&1),
+            Some(&Source::File(PathBuf::from("in.txt")))
+        );
+        assert_eq!(parsed.input_overrides.get(&2), Some(&Source::Stdin));
+        assert_eq!(parsed.jobs, Some(3));
+        assert_eq!(parsed.year, Some(2024));
+        assert_eq!(parsed.action, Action::Run);
+
+        let defaults = args("").unwrap();
+        assert_eq!(defaults.days, DaySelection::All);
+        assert_eq!(defaults.parts, Parts::Both);
+        assert_eq!(defaults.year, None);
+        assert_eq!(defaults.jobs, None);
+
+        assert_eq!(parse(["-h".to_owned()]).unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn later_flags_win() {
+        let parsed = args("--part a --part=b --input 1=x --input 1=y").unwrap();
+        assert_eq!(parsed.parts, Parts::B);
+        assert_eq!(
+            parsed.input_overrides.get(&1),
+            Some(&Source::File(PathBuf::from("y")))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert_eq!(error("--frobnicate"), "Unknown option: --frobnicate");
+        assert_eq!(error("--frobnicate=1"), "Unknown option: --frobnicate=1");
+        assert_eq!(error("--part"), "Missing value for --part");
+        assert_eq!(error("--part c"), "Unknown part: c (expected a, b or both)");
+        assert_eq!(error("1 2"), "Days were specified more than once: 2");
+        assert_eq!(
+            error("--input 1"),
+            "Invalid input override: 1 (expected DAY=PATH)"
+        );
+        assert_eq!(
+            error("--input 1=- --stdin"),
+            "Only one day can read its input from stdin"
+        );
+        assert_eq!(error("-j 0"), "--jobs must be at least 1");
+        assert_eq!(error("-j x"), "Invalid value for --jobs: x");
+        assert_eq!(
+            error("--runs 3"),
+            "--runs can only be used together with --bench"
+        );
+        assert_eq!(error("--grid"), "Unknown option: --grid");
+        assert_eq!(
+            error("--year 2014"),
+            "Year 2014 does not exist, the first year was 2015"
+        );
+    }
+
+    #[test]
+    fn subcommands() {
+        assert_eq!(args("fetch 1-3").unwrap().action, Action::Fetch);
+        assert_eq!(
+            args("submit 3 b 42").unwrap().action,
+            Action::Submit(Submission {
+                day: 3,
+                part: Part::B,
+                answer: Some("42".to_owned()),
+            })
+        );
+        assert_eq!(
+            args("new 4 --grid --title=Trees").unwrap().action,
+            Action::New(NewDay {
+                day: 4,
+                title: Some("Trees".to_owned()),
+                template: Template::Grid,
+            })
+        );
+        assert_eq!(error("submit 3"), "submit needs a day and a part");
+        assert_eq!(error("submit 3 b 42 43"), "Unexpected argument: 43");
+        assert_eq!(error("new 4 5"), "new needs exactly one day");
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_common::Solutions;
+use aoc_common::net::stub::{Received, StubServer};
+use aoc_common::net::{HttpClient, PlainHttpClient};
+use rustc_hash::FxHashMap;
+
+use crate::answers::Answers;
+use crate::input::Inputs;
+use crate::registry::Entry;
+
+/// Runs `input` through the registered solver and checks both parts against the expected answers.
+pub fn check_answers(
+    entry: &Entry,
+    input: &str,
+    expected_a: impl Display,
+    expected_b: impl Display,
+) {
+    let (solution_a, solution_b) = solve(entry, input);
+    assert_eq!(solution_a, expected_a.to_string(), "{}a", name(entry));
+    assert_eq!(solution_b, expected_b.to_string(), "{}b", name(entry));
+}
+
+/// Runs `input` through the registered solver and checks that it fails with `expected`.
+pub fn check_error(entry: &Entry, input: &str, expected: &str) {
+    match entry.runnable.solve(input) {
+        Ok(solutions) => panic!("{} accepted bad input: {solutions:?}", name(entry)),
+        Err(e) => assert_eq!(e.to_string(), expected, "{}", name(entry)),
+    }
+}
+
+/// Checks the real input against the recorded answers. Either may be absent locally, in which case
+/// the check is skipped.
+pub fn check_real_input(entry: &Entry) {
+    let expected = Answers::new(None)
+        .load(entry.year, entry.day)
+        .unwrap_or_else(|e| panic!("{e}"));
+    if expected == Solutions::default() {
+        eprintln!("Skipping {}: no recorded answers", name(entry));
+        return;
+    }
+    let input = match Inputs::new(None, FxHashMap::default()).load(entry.year, entry.day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Skipping {}: {e}", name(entry));
+            return;
+        }
+    };
+
+    let (solution_a, solution_b) = solve(entry, &input);
+    if let Some(expected_a) = &expected.a {
+        assert_eq!(&solution_a, expected_a, "{}a", name(entry));
+    }
+    if let Some(expected_b) = &expected.b {
+        assert_eq!(&solution_b, expected_b, "{}b", name(entry));
+    }
+}
+
+fn solve(entry: &Entry, input: &str) -> (String, String) {
+    entry
+        .runnable
+        .solve(input)
+        .unwrap_or_else(|e| panic!("Error occurred running {}: {e}", name(entry)))
+}
+
+fn name(entry: &Entry) -> String {
+    format!("{} day {}", entry.year, entry.day)
+}
+
+/// A fresh directory for a test to write to, named after the test and the process so parallel
+/// test runs don't clash. It isn't created, only emptied from a previous run.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Starts a stub of the puzzle site that answers with `respond`, and connects a client of the site
+/// to it with `new`, like `InputCache::new`. The client keeps its files in a fresh `temp_dir`
+/// named after `name` and sends its requests through `client`, at most one every `throttle`.
+pub fn stub_site<T>(
+    name: &str,
+    respond: impl Fn(&Received) -> (u16, String) + Send + 'static,
+    new: impl FnOnce(PathBuf, String, Box<dyn HttpClient>, Duration) -> T,
+    client: PlainHttpClient,
+    throttle: Duration,
+) -> (StubServer, PathBuf, T) {
+    let server = StubServer::start(respond);
+    let dir = temp_dir(name);
+    let connected = new(
+        dir.clone(),
+        server.base_url.clone(),
+        Box::new(client),
+        throttle,
+    );
+    (server, dir, connected)
+}
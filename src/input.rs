@@ -0,0 +1,122 @@
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use rustc_hash::FxHashMap;
+use tracing::warn;
+
+use crate::cache::InputCache;
+
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+pub const DEFAULT_INPUTS: &str = "inputs";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn parse(value: &str) -> Self {
+        if value == "-" {
+            Source::Stdin
+        } else {
+            Source::File(PathBuf::from(value))
+        }
+    }
+}
+
+/// Decides where the input for each day comes from. Days without an override are read from
+/// `<dir>/<year>/<day>.txt`, falling back to the input cache if there is one. Overrides are keyed
+/// by day only, as a single run only covers a single year.
+pub struct Inputs {
+    dir: PathBuf,
+    overrides: FxHashMap<u32, Source>,
+    cache: Option<InputCache>,
+    fetch: bool,
+}
+
+impl Inputs {
+    /// Uses `dir` if given, otherwise falls back to `$AOC_INPUTS` and then to `inputs`.
+    pub fn new(dir: Option<PathBuf>, overrides: FxHashMap<u32, Source>) -> Self {
+        let dir = dir
+            .or_else(|| std::env::var_os(INPUTS_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS));
+        Self {
+            dir,
+            overrides,
+            cache: None,
+            fetch: false,
+        }
+    }
+
+    /// Looks up inputs missing from the inputs directory in `cache`, downloading them into the
+    /// cache if `fetch` is set.
+    pub fn with_cache(mut self, cache: InputCache, fetch: bool) -> Self {
+        self.cache = Some(cache);
+        self.fetch = fetch;
+        self
+    }
+
+    pub fn source(&self, year: u32, day: u32) -> Source {
+        self.overrides.get(&day).cloned().unwrap_or_else(|| {
+            Source::File(self.dir.join(year.to_string()).join(format!("{day}.txt")))
+        })
+    }
+
+    pub fn load(&self, year: u32, day: u32) -> Result<String> {
+        match self.source(year, day) {
+            Source::File(path) => match std::fs::read_to_string(&path) {
+                Ok(input) => Ok(input),
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    self.hint_at_old_layout(&path, day);
+                    self.load_missing(&path, year, day)
+                }
+                Err(e) => Err(e).with_context(|| {
+                    format!("Could not read input for day {day} at {}", path.display())
+                }),
+            },
+            Source::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .with_context(|| format!("Could not read input for day {day} from stdin"))?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Inputs used to be stored as `<dir>/<day>.txt` before there were years, which is ignored
+    /// now. Points out where such a file should go instead.
+    fn hint_at_old_layout(&self, path: &Path, day: u32) {
+        let old = self.dir.join(format!("{day}.txt"));
+        if !self.overrides.contains_key(&day) && old.is_file() {
+            warn!(
+                "Ignoring {}, inputs are stored per year now, move it to {}",
+                old.display(),
+                path.display()
+            );
+        }
+    }
+
+    /// Falls back to the cache for an input that isn't in the inputs directory.
+    fn load_missing(&self, path: &Path, year: u32, day: u32) -> Result<String> {
+        match &self.cache {
+            Some(cache) if !self.overrides.contains_key(&day) => {
+                if self.fetch {
+                    return cache.get(year, day);
+                }
+                if let Some(input) = cache.cached(year, day)? {
+                    return Ok(input);
+                }
+                bail!(
+                    "Input missing for day {day} at {} and not cached at {}, use \
+                     --fetch to download it",
+                    path.display(),
+                    cache.path(year, day).display()
+                )
+            }
+            _ => bail!("Input missing for day {day} at {}", path.display()),
+        }
+    }
+}
@@ -1,4 +1,3 @@
-#![feature(array_windows)]
 #![feature(int_roundings)]
 #![feature(iter_map_windows)]
 #![feature(pattern)]
@@ -6,79 +5,223 @@
 #![feature(gen_blocks)]
 #![warn(clippy::pedantic)]
 #![allow(
+    unused_features,
     clippy::cast_possible_truncation,
     clippy::cast_possible_wrap,
     clippy::cast_precision_loss,
     clippy::cast_sign_loss
 )]
 
-use std::time::Instant;
-
-use common::{Day, Runnable};
-use tracing::{Level, info, info_span};
-
-mod common;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-// mod day8;
-// mod day9;
-
-// mod day10;
-// mod day11;
-// mod day12;
-// mod day13;
-// mod day14;
-// mod day15;
-// mod day16;
-// mod day17;
-// mod day18;
-// mod day19;
-
-// mod day20;
-// mod day21;
-// mod day22;
-// mod day23;
-// mod day24;
-// mod day25;
-
-fn main() {
-    tracing_subscriber::fmt::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-    let span = info_span!("All days");
-    span.in_scope(|| {
-        let start = Instant::now();
-        Day::Combined(day1::main).run("day 1");
-        Day::Combined(day2::main).run("day 2");
-        Day::Combined(day3::main).run("day 3");
-        Day::Combined(day4::main).run("day 4");
-        Day::Combined(day5::main).run("day 5");
-        Day::Combined(day6::main).run("day 6");
-        Day::Combined(day7::main).run("day 7");
-        // Day::Combined(day8::main).run("day 8");
-        // Day::Combined(day9::main).run("day 9");
-        // Day::Combined(day10::main).run("day 10");
-        // Day::Combined(day11::main).run("day 11");
-        // Day::Combined(day12::main).run("day 12");
-        // Day::Combined(day13::main).run("day 13");
-        // Day::Combined(day14::main).run("day 14");
-        // Day::Combined(day15::main).run("day 15");
-        // Day::Combined(day16::main).run("day 16");
-        // Day::Combined(day17::main).run("day 17");
-        // Day::Combined(day18::main).run("day 18");
-        // Day::Combined(day19::main).run("day 19");
-        // Day::Combined(day20::main).run("day 20");
-        // Day::Combined(day21::main).run("day 21");
-        // Day::Combined(day22::main).run("day 22");
-        // Day::Combined(day23::main).run("day 23");
-        // Day::Combined(day24::main).run("day 24");
-        // Day::Combined(day25::main).run("day 25");
-
-        info!("Took {:#?}", start.elapsed());
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use answers::Answers;
+use anyhow::Result;
+use aoc_common::net;
+use cache::InputCache;
+use cli::Action;
+use input::{Inputs, Source};
+use registry::Entry;
+use report::Format;
+use runner::Runner;
+use submit::Submitter;
+use tracing::{Level, error, info, info_span};
+
+mod answers;
+mod bench;
+mod cache;
+mod cli;
+mod input;
+mod registry;
+mod report;
+mod runner;
+mod scaffold;
+mod submit;
+
+#[cfg(test)]
+mod harness;
+
+registry::years! {
+    y2025,
+}
+
+fn main() -> ExitCode {
+    let args = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli::Command::Run(args)) => *args,
+        Ok(cli::Command::Help) => {
+            println!("{}", cli::USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let subscriber = tracing_subscriber::fmt::fmt().with_max_level(Level::INFO);
+    match args.format {
+        // Keep stdout clean for the JSON records
+        Format::Json => subscriber.with_writer(std::io::stderr).init(),
+        Format::Text => subscriber.init(),
+    }
+
+    let year = match args.year.map_or_else(|| registry::default_year(YEARS), Ok) {
+        Ok(year) => year,
+        Err(e) => {
+            error!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let days = YEARS
+        .iter()
+        .find(|registered| registered.year == year)
+        .map_or(&[][..], |registered| registered.days);
+
+    if let Action::New(new_day) = &args.action {
+        let answers = Answers::new(args.answers);
+        return exit_code(scaffold::create(Path::new("."), year, &answers, new_day).map(|_| ()));
+    }
+
+    let implemented: Vec<u32> = days.iter().map(|entry| entry.day).collect();
+    let base_url = args
+        .base_url
+        .or_else(|| std::env::var(net::BASE_URL_ENV).ok())
+        .unwrap_or_else(|| net::DEFAULT_BASE_URL.to_owned());
+    let user_agent =
+        std::env::var(net::USER_AGENT_ENV).unwrap_or_else(|_| net::DEFAULT_USER_AGENT.to_owned());
+    let cache_dir = cache::resolve_dir(args.cache);
+    let cache = InputCache::new(
+        cache_dir.clone(),
+        base_url.clone(),
+        net::client_for(&base_url, &user_agent),
+        args.throttle,
+    );
+
+    if args.action == Action::Fetch {
+        return status(cache.fetch_all(year, &args.days.days(&implemented)));
+    }
+
+    if let Action::Submit(submission) = &args.action {
+        let submitter = Submitter::new(
+            cache_dir,
+            base_url.clone(),
+            net::client_for(&base_url, &user_agent),
+            args.throttle,
+        );
+        let inputs = Inputs::new(args.inputs, args.input_overrides).with_cache(cache, args.fetch);
+        let entry = days.iter().find(|entry| entry.day == submission.day);
+        return status(submit::run(year, submission, entry, &inputs, &submitter));
+    }
+
+    if days.is_empty() {
+        error!("No days are implemented for {year}");
+        return ExitCode::FAILURE;
+    }
+    let selected = match args.days.resolve(&implemented) {
+        Ok(selected) => selected,
+        Err(e) => {
+            error!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut overrides = args.input_overrides;
+    if args.stdin {
+        let [day] = selected[..] else {
+            error!("--stdin requires exactly one day to be selected");
+            return ExitCode::FAILURE;
+        };
+        overrides.insert(day, Source::Stdin);
+    }
+    let runner = Runner {
+        inputs: Inputs::new(args.inputs, overrides).with_cache(cache, args.fetch),
+        answers: Answers::new(args.answers),
+        parts: args.parts,
+        timeout: args.timeout,
+    };
+
+    let entries: Vec<_> = days
+        .iter()
+        .filter(|entry| selected.contains(&entry.day))
+        .collect();
+
+    if let Some(config) = &args.bench {
+        let span = info_span!("Benchmark");
+        return status(span.in_scope(|| bench::run(&entries, &runner.inputs, args.parts, config)));
+    }
+
+    let span = info_span!("All days", year);
+    let failures = span.in_scope(|| run_days(&runner, &entries, args.jobs, args.format));
+
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        error!("{failures} day(s) failed");
+        ExitCode::FAILURE
+    }
+}
+
+/// Runs and reports the given days, returning how many of them failed.
+fn run_days(runner: &Runner, entries: &[&Entry], jobs: Option<usize>, format: Format) -> usize {
+    let start = Instant::now();
+    let start_cpu = cpu_time();
+    let mut failures = 0;
+    let mut timeouts = 0;
+    runner.run_all(entries, jobs, |report| {
+        report.print(format);
+        if !report.success() {
+            failures += 1;
+        }
+        if report.timed_out {
+            timeouts += 1;
+        }
     });
+
+    let wall_time = start.elapsed();
+    if let (Some(start_cpu), Some(end_cpu)) = (start_cpu, cpu_time()) {
+        let cpu_time = end_cpu.saturating_sub(start_cpu);
+        info!("Took {:#?}, total CPU time {:#?}", wall_time, cpu_time);
+    } else {
+        info!("Took {:#?}", wall_time);
+    }
+    if let Some(timeout) = runner.timeout {
+        info!(
+            "Time limit {:#?} per day, {} day(s) timed out",
+            timeout, timeouts
+        );
+    }
+    failures
+}
+
+/// The CPU time used by all threads of the process so far, which adds up the time of days that
+/// run in parallel with --jobs.
+fn cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: getrusage only writes to `usage`, and it is only read if the call succeeded
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    // SAFETY: initialized by the successful call above
+    let usage = unsafe { usage.assume_init() };
+    let time = |time: libc::timeval| Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1000);
+    Some(time(usage.ru_utime) + time(usage.ru_stime))
+}
+
+fn status(success: bool) -> ExitCode {
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn exit_code(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error!("{e:#}");
+            ExitCode::FAILURE
+        }
+    }
 }
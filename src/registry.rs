@@ -0,0 +1,85 @@
+use anyhow::{Result, anyhow};
+use aoc_common::runner::catch_panic;
+use aoc_common::{DynDay, Parts, Run, parse};
+
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+/// A day registers itself by exposing a `pub const DAY: Entry` from its module, the module is
+/// then listed once in the `days!` invocation in the `mod.rs` of its year.
+#[derive(Copy, Clone)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub runnable: &'static DynDay,
+}
+
+impl Entry {
+    pub const fn new(year: u32, day: u32, title: &'static str, runnable: &'static DynDay) -> Self {
+        Self {
+            year,
+            day,
+            title,
+            runnable,
+        }
+    }
+
+    /// Runs the day behind a panic boundary. Parse errors are tagged with the day.
+    pub fn run(&self, input: &str, parts: Parts) -> Result<Run> {
+        catch_panic(|| self.runnable.run(input, parts)).map_err(|e| parse::attach_day(e, self.day))
+    }
+}
+
+/// The days implemented for a single year, each year lives in its own `y<year>` module which is
+/// listed in the `years!` invocation in `main.rs`.
+pub struct Year {
+    pub year: u32,
+    pub days: &'static [Entry],
+}
+
+/// The year to use when none is given: `$AOC_YEAR` if it is set, otherwise the latest year in
+/// `years`.
+pub fn default_year(years: &[&Year]) -> Result<u32> {
+    if let Ok(year) = std::env::var(YEAR_ENV) {
+        return year
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("Invalid year in ${YEAR_ENV}: {year}"));
+    }
+    years
+        .iter()
+        .map(|year| year.year)
+        .max()
+        .ok_or_else(|| anyhow!("No years are registered"))
+}
+
+/// Declares each day module of a year and collects its `DAY` entry into the `YEAR` registry, so a
+/// module can't be declared without also being run. Entries are checked to belong to the year at
+/// compile time.
+macro_rules! days {
+    ($year:literal; $($module:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        $(const _: () = assert!(
+            $module::DAY.year == $year,
+            concat!(stringify!($module), " is registered for the wrong year")
+        );)*
+
+        pub(crate) static YEAR: crate::registry::Year = crate::registry::Year {
+            year: $year,
+            days: &[$($module::DAY),*],
+        };
+    };
+}
+
+/// Declares each year module and collects them into the `YEARS` registry.
+macro_rules! years {
+    ($($module:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        pub(crate) static YEARS: &[&registry::Year] = &[$(&$module::YEAR),*];
+    };
+}
+
+pub(crate) use days;
+pub(crate) use years;
@@ -0,0 +1,199 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc_common::{Part, Timing, Verdict};
+use tracing::{error, info};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Clone, Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Everything that is known about a single day after running it, independent of how it is
+/// presented.
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub duration: Duration,
+    pub timings: Vec<Timing>,
+    pub parts: Vec<PartReport>,
+    pub error: Option<String>,
+    pub timed_out: bool,
+}
+
+impl DayReport {
+    pub fn success(&self) -> bool {
+        self.error.is_none()
+            && self
+                .parts
+                .iter()
+                .all(|part| !matches!(part.verdict, Verdict::Wrong { .. }))
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.timed_out {
+            "timeout"
+        } else if self.error.is_some() {
+            "error"
+        } else if self.success() {
+            "ok"
+        } else {
+            "wrong"
+        }
+    }
+
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => self.log(),
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+
+    pub fn log(&self) {
+        let name = format!("day {}", self.day);
+        info!("Day {}: {}", self.day, self.title);
+        for timing in &self.timings {
+            info!("{} took {:#?}", timing.name, timing.duration);
+        }
+        for part in &self.parts {
+            match &part.verdict {
+                Verdict::Correct => {
+                    info!("Solution {}{}: {} (correct)", name, part.part, part.answer);
+                }
+                Verdict::Wrong { expected } => error!(
+                    "Solution {}{}: {} (wrong, expected {})",
+                    name, part.part, part.answer, expected
+                ),
+                Verdict::Unknown => info!("Solution {}{}: {}", name, part.part, part.answer),
+            }
+        }
+        if let Some(e) = &self.error {
+            error!("Error occurred running {}: {}", name, e);
+        }
+    }
+
+    /// Formats the report as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        write!(
+            json,
+            "{{\"year\":{},\"day\":{},\"title\":{},\"status\":\"{}\",\"duration_ns\":{}",
+            self.year,
+            self.day,
+            json_string(self.title),
+            self.status(),
+            self.duration.as_nanos()
+        )
+        .unwrap();
+
+        json.push_str(",\"timings\":[");
+        for (i, timing) in self.timings.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"name\":{},\"duration_ns\":{}}}",
+                json_string(&timing.name),
+                timing.duration.as_nanos()
+            )
+            .unwrap();
+        }
+
+        json.push_str("],\"parts\":[");
+        for (i, part) in self.parts.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"part\":\"{}\",\"answer\":{}",
+                part.part,
+                json_string(&part.answer)
+            )
+            .unwrap();
+            match &part.verdict {
+                Verdict::Correct => json.push_str(",\"status\":\"correct\"}"),
+                Verdict::Wrong { expected } => write!(
+                    json,
+                    ",\"status\":\"wrong\",\"expected\":{}}}",
+                    json_string(expected)
+                )
+                .unwrap(),
+                Verdict::Unknown => json.push_str(",\"status\":\"unknown\"}"),
+            }
+        }
+        json.push(']');
+
+        match &self.error {
+            Some(e) => write!(json, ",\"error\":{}}}", json_string(e)).unwrap(),
+            None => json.push_str(",\"error\":null}"),
+        }
+        json
+    }
+}
+
+fn json_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len() + 2);
+    escaped.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_common::{Part, Timing, Verdict};
+
+    use super::{DayReport, PartReport};
+
+    #[test]
+    fn json_record() {
+        let report = DayReport {
+            year: 2025,
+            day: 2,
+            title: "Gift \"Shop\"",
+            duration: Duration::from_nanos(1500),
+            timings: vec![Timing::new("Part a", Duration::from_micros(1))],
+            parts: vec![PartReport {
+                part: Part::A,
+                answer: "12".to_owned(),
+                verdict: Verdict::Wrong {
+                    expected: "13".to_owned(),
+                },
+            }],
+            error: Some("bad\ninput".to_owned()),
+            timed_out: false,
+        };
+        assert_eq!(
+            report.to_json(),
+            "{\"year\":2025,\"day\":2,\"title\":\"Gift \\\"Shop\\\"\",\"status\":\"error\",\"duration_ns\":1500,\
+             \"timings\":[{\"name\":\"Part a\",\"duration_ns\":1000}],\
+             \"parts\":[{\"part\":\"a\",\"answer\":\"12\",\"status\":\"wrong\",\"expected\":\"13\"}],\
+             \"error\":\"bad\\ninput\"}"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use aoc_common::{Parts, Run, Verdict};
+
+use crate::answers::Answers;
+use crate::input::Inputs;
+use crate::registry::Entry;
+use crate::report::{DayReport, PartReport};
+
+pub struct Runner {
+    pub inputs: Inputs,
+    pub answers: Answers,
+    pub parts: Parts,
+    /// How long a day may run before it is reported as timed out and abandoned.
+    pub timeout: Option<Duration>,
+}
+
+impl Runner {
+    /// Loads the input and known answers for a day, runs it and checks the results. Any error along
+    /// the way ends up in the report rather than being returned.
+    pub fn run(&self, entry: &Entry) -> DayReport {
+        let mut report = DayReport {
+            year: entry.year,
+            day: entry.day,
+            title: entry.title,
+            duration: Duration::default(),
+            timings: Vec::new(),
+            parts: Vec::new(),
+            error: None,
+            timed_out: false,
+        };
+
+        let (input, expected) = match (
+            self.inputs.load(entry.year, entry.day),
+            self.answers.load(entry.year, entry.day),
+        ) {
+            (Ok(input), Ok(expected)) => (input, expected),
+            (Err(e), _) | (_, Err(e)) => {
+                report.error = Some(format!("{e:#}"));
+                return report;
+            }
+        };
+
+        let now = Instant::now();
+        let result = match self.timeout {
+            None => entry.run(&input, self.parts),
+            Some(limit) => {
+                let result = Self::run_with_timeout(entry, input, self.parts, limit);
+                report.timed_out = result.is_none();
+                result.unwrap_or_else(|| Err(anyhow!("Timed out after {limit:#?}")))
+            }
+        };
+        report.duration = now.elapsed();
+
+        match result {
+            Ok(run) => {
+                report.parts = run
+                    .solutions
+                    .iter()
+                    .map(|(part, answer)| PartReport {
+                        part,
+                        answer: answer.to_owned(),
+                        verdict: Verdict::check(expected.get(part), answer),
+                    })
+                    .collect();
+                report.timings = run.timings;
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+        report
+    }
+
+    /// Runs the day on its own thread and stops waiting for it after `limit`, returning `None` if it
+    /// didn't finish in time. There is no way to stop a thread, so a day that timed out is left
+    /// running in the background until the process exits.
+    fn run_with_timeout(
+        entry: &Entry,
+        input: String,
+        parts: Parts,
+        limit: Duration,
+    ) -> Option<Result<Run>> {
+        let entry = *entry;
+        let (sender, receiver) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name(format!("day {}", entry.day))
+            .spawn(move || {
+                let _ = sender.send(entry.run(&input, parts));
+            });
+        if let Err(e) = spawned {
+            return Some(Err(e.into()));
+        }
+
+        match receiver.recv_timeout(limit) {
+            Ok(result) => Some(result),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                Some(Err(anyhow!("Day {} stopped without a result", entry.day)))
+            }
+        }
+    }
+
+    /// Runs all entries, on `jobs` threads if given, handing the reports to `on_report` in the
+    /// order of `entries` as soon as they and all reports before them are done.
+    pub fn run_all(
+        &self,
+        entries: &[&Entry],
+        jobs: Option<usize>,
+        mut on_report: impl FnMut(DayReport),
+    ) {
+        let Some(jobs) = jobs.filter(|&jobs| jobs > 1 && entries.len() > 1) else {
+            for entry in entries {
+                on_report(self.run(entry));
+            }
+            return;
+        };
+
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(entries.len()) {
+                let sender = sender.clone();
+                let next = &next;
+                scope.spawn(move || {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(entry) = entries.get(i) else {
+                            break;
+                        };
+                        if sender.send((i, self.run(entry))).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut pending = BTreeMap::new();
+            let mut reported = 0;
+            for (i, report) in receiver {
+                pending.insert(i, report);
+                while let Some(report) = pending.remove(&reported) {
+                    on_report(report);
+                    reported += 1;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::Duration;
+
+    use aoc_common::{Day, Parts};
+    use rustc_hash::FxHashMap;
+
+    use super::Runner;
+    use crate::answers::Answers;
+    use crate::harness::temp_dir;
+    use crate::input::{Inputs, Source};
+    use crate::registry::Entry;
+
+    static STUCK: Day<u8, u8> = Day::Combined(|_| {
+        loop {
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+    static SLOW: Day<u8, u8> = Day::Combined(|_| {
+        thread::sleep(Duration::from_millis(100));
+        Ok((1, 1))
+    });
+    static FAST: Day<u8, u8> = Day::Combined(|_| Ok((2, 2)));
+
+    /// A runner that reads `Cargo.toml` as the input of `days`, with no recorded answers so local
+    /// answers files don't get in the way.
+    fn runner(
+        name: &str,
+        days: impl IntoIterator<Item = u32>,
+        timeout: Option<Duration>,
+    ) -> Runner {
+        let sources: FxHashMap<_, _> = days
+            .into_iter()
+            .map(|day| (day, Source::File(PathBuf::from("Cargo.toml"))))
+            .collect();
+        Runner {
+            inputs: Inputs::new(None, sources),
+            answers: Answers::new(Some(temp_dir(name))),
+            parts: Parts::Both,
+            timeout,
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let entry = Entry::new(2025, 1, "Stuck", &STUCK);
+        let runner = runner("timeout", [1], Some(Duration::from_millis(50)));
+
+        let report = runner.run(&entry);
+        assert!(report.timed_out);
+        assert_eq!(report.status(), "timeout");
+        assert_eq!(report.error.as_deref(), Some("Timed out after 50ms"));
+    }
+
+    #[test]
+    fn parallel_reports_in_day_order() {
+        // The slow days finish after the fast ones that were started later
+        let entries = [
+            Entry::new(2025, 1, "Slow", &SLOW),
+            Entry::new(2025, 2, "Fast", &FAST),
+            Entry::new(2025, 3, "Fast", &FAST),
+            Entry::new(2025, 4, "Slow", &SLOW),
+            Entry::new(2025, 5, "Fast", &FAST),
+        ];
+        let entries: Vec<_> = entries.iter().collect();
+        let runner = runner("parallel", 1..=5, None);
+
+        let mut reported = Vec::new();
+        runner.run_all(&entries, Some(3), |report| {
+            assert!(report.success(), "{:?}", report.error);
+            reported.push((report.day, report.parts[0].answer.clone()));
+        });
+        let answer = |day, answer: &str| (day, answer.to_owned());
+        assert_eq!(
+            reported,
+            [
+                answer(1, "1"),
+                answer(2, "2"),
+                answer(3, "2"),
+                answer(4, "1"),
+                answer(5, "2")
+            ]
+        );
+    }
+}
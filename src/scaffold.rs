@@ -0,0 +1,331 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use tracing::info;
+
+use crate::answers::Answers;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Template {
+    /// Iterates over the input line by line.
+    Lines,
+    /// Reads the input into an `InfiniteGrid`.
+    Grid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewDay {
+    pub day: u32,
+    pub title: Option<String>,
+    pub template: Template,
+}
+
+const LINES_TEMPLATE: &str = r#"use aoc_common::{Day, Lines};
+use crate::registry::Entry;
+use anyhow::Result;
+
+pub const DAY: Entry = Entry::new(@YEAR@, @DAY@, "@TITLE@", &Day::CombinedLines(main));
+
+// Until the day can fail to parse, the Result is only there to fit `Day`
+#[allow(clippy::unnecessary_wraps)]
+pub fn main(lines: Lines) -> Result<(usize, usize)> {
+    for line in lines {
+        let _line = line.trim();
+    }
+
+    // Not solved yet, 0 is never mistaken for an answer
+    Ok((0, 0))
+}
+"#;
+
+const GRID_TEMPLATE: &str = r#"use aoc_common::{CharConvertable, Day, InfiniteGrid, ParseError, SignedCoordinate};
+use crate::registry::Entry;
+use anyhow::Result;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Data {
+    Wall,
+}
+
+impl CharConvertable for Data {
+    fn to_char(option: Option<&Self>) -> char {
+        match option {
+            None => '.',
+            Some(Data::Wall) => '#',
+        }
+    }
+
+    fn from_char(c: char) -> Result<Option<Self>, ParseError> {
+        Ok(match c {
+            '.' => None,
+            '#' => Some(Data::Wall),
+            _ => return Err(ParseError::unexpected_char(c)),
+        })
+    }
+}
+
+pub const DAY: Entry = Entry::new(@YEAR@, @DAY@, "@TITLE@", &Day::Combined(main));
+
+pub fn main(input: &str) -> Result<(usize, usize)> {
+    let _grid = InfiniteGrid::<SignedCoordinate, Data, false>::read(input.lines())?;
+
+    // Not solved yet, 0 is never mistaken for an answer
+    Ok((0, 0))
+}
+"#;
+
+const TESTS_TEMPLATE: &str = r#"
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "fill in the example and its answers"]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 0, 0);
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
+"#;
+
+const YEAR_TEMPLATE: &str = "use crate::registry;
+
+registry::days! {
+    @YEAR@;
+}
+";
+
+/// Renders the source of a new day module.
+pub fn render(year: u32, new_day: &NewDay) -> String {
+    let template = match new_day.template {
+        Template::Lines => LINES_TEMPLATE,
+        Template::Grid => GRID_TEMPLATE,
+    };
+    let title = new_day
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", new_day.day));
+    let title = format!("{title:?}");
+    let mut source = template
+        .replace("@YEAR@", &year.to_string())
+        .replace("@DAY@", &new_day.day.to_string())
+        .replace("\"@TITLE@\"", &title);
+    source.push_str(TESTS_TEMPLATE);
+    source
+}
+
+/// Adds the module `<prefix><number>` to the `registry::<registry>!` invocation in `source`,
+/// keeping the modules sorted by number. Anything before a `;` in the invocation, like the year
+/// in `days!`, is kept as is. Fails if the module is already registered.
+pub fn register(source: &str, registry: &str, prefix: &str, number: u32) -> Result<String> {
+    let invocation = format!("registry::{registry}! {{");
+    let start = source
+        .find(&invocation)
+        .ok_or_else(|| anyhow!("Could not find the {registry}! invocation"))?
+        + invocation.len();
+    let end = start
+        + source[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Could not find the end of the {registry}! invocation"))?;
+    let (header, modules) = match source[start..end].split_once(';') {
+        Some((header, modules)) => (Some(header.trim()), modules),
+        None => (None, &source[start..end]),
+    };
+
+    let mut numbers = modules
+        .split(',')
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .map(|module| {
+            module
+                .strip_prefix(prefix)
+                .and_then(|number| number.parse::<u32>().ok())
+                .ok_or_else(|| anyhow!("Unexpected module in {registry}!: {module}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if numbers.contains(&number) {
+        bail!("{prefix}{number} is already registered");
+    }
+    numbers.push(number);
+    numbers.sort_unstable();
+
+    let mut registered = String::from("\n");
+    if let Some(header) = header {
+        writeln!(registered, "    {header};")?;
+    }
+    for number in numbers {
+        writeln!(registered, "    {prefix}{number},")?;
+    }
+    Ok(format!(
+        "{}{registered}{}",
+        &source[..start],
+        &source[end..]
+    ))
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| {
+        format!(
+            "Could not read {}, run this from the root of the repository",
+            path.display()
+        )
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents).with_context(|| format!("Could not write {}", path.display()))
+}
+
+/// Creates `src/y<year>/day<day>.rs` and an empty answers file under `root`, and registers the
+/// day in `src/y<year>/mod.rs`. A year that doesn't exist yet gets its module created and
+/// registered in `src/main.rs` as well. Nothing is written if the day or its answers already
+/// exist.
+pub fn create(root: &Path, year: u32, answers: &Answers, new_day: &NewDay) -> Result<Vec<PathBuf>> {
+    let main_path = root.join("src").join("main.rs");
+    let year_dir = root.join("src").join(format!("y{year}"));
+    let year_path = year_dir.join("mod.rs");
+    let module_path = year_dir.join(format!("day{}.rs", new_day.day));
+    let answers_path = answers.path(year, new_day.day);
+    for path in [&module_path, &answers_path] {
+        if path.exists() {
+            bail!("Refusing to overwrite {}", path.display());
+        }
+    }
+
+    let mut files = Vec::new();
+    let year_source = if year_path.exists() {
+        read(&year_path)?
+    } else {
+        let main = register(&read(&main_path)?, "years", "y", year)?;
+        files.push((main_path, main));
+        YEAR_TEMPLATE.replace("@YEAR@", &year.to_string())
+    };
+    files.push((
+        year_path,
+        register(&year_source, "days", "day", new_day.day)?,
+    ));
+    files.push((module_path, render(year, new_day)));
+    // Both answers unknown
+    files.push((answers_path, "\n\n".to_owned()));
+
+    let mut created = Vec::new();
+    for (path, contents) in files.into_iter().rev() {
+        write(&path, &contents)?;
+        info!("Wrote {}", path.display());
+        created.push(path);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NewDay, Template, create, register, render};
+    use crate::answers::Answers;
+    use crate::harness::temp_dir;
+
+    const MAIN: &str = "mod cli;\n\nregistry::years! {\n    y2024,\n}\n\nfn main() {}\n";
+    const YEAR: &str =
+        "use crate::registry;\n\nregistry::days! {\n    2024;\n    day1,\n    day10,\n}\n";
+
+    #[test]
+    fn registers_in_order() {
+        assert_eq!(
+            register(YEAR, "days", "day", 9).unwrap(),
+            "use crate::registry;\n\nregistry::days! {\n    2024;\n    day1,\n    day9,\n    day10,\n}\n"
+        );
+        assert_eq!(
+            register(YEAR, "days", "day", 10).unwrap_err().to_string(),
+            "day10 is already registered"
+        );
+        assert_eq!(
+            register(MAIN, "years", "y", 2015).unwrap(),
+            "mod cli;\n\nregistry::years! {\n    y2015,\n    y2024,\n}\n\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn renders_templates() {
+        let new_day = NewDay {
+            day: 8,
+            title: Some("Say \"hi\"".to_owned()),
+            template: Template::Lines,
+        };
+        let source = render(2024, &new_day);
+        assert!(source.contains(r#"Entry::new(2024, 8, "Say \"hi\"", &Day::CombinedLines(main))"#));
+        assert!(source.contains("mod tests"));
+        assert!(source.contains("Ok((0, 0))"));
+
+        let source = render(
+            2024,
+            &NewDay {
+                title: None,
+                template: Template::Grid,
+                ..new_day
+            },
+        );
+        assert!(source.contains(r#"Entry::new(2024, 8, "Day 8", &Day::Combined(main))"#));
+        assert!(source.contains("InfiniteGrid"));
+        assert!(source.contains("Ok((0, 0))"));
+    }
+
+    #[test]
+    fn creates_years_and_refuses_to_overwrite() {
+        let root = temp_dir("scaffold");
+        std::fs::create_dir_all(root.join("src/y2024")).unwrap();
+        std::fs::write(root.join("src/main.rs"), MAIN).unwrap();
+        std::fs::write(root.join("src/y2024/mod.rs"), YEAR).unwrap();
+        let answers = Answers::new(Some(root.join("answers")));
+        let new_day = NewDay {
+            day: 3,
+            title: None,
+            template: Template::Lines,
+        };
+
+        create(&root, 2024, &answers, &new_day).unwrap();
+        assert!(root.join("src/y2024/day3.rs").exists());
+        assert_eq!(
+            std::fs::read_to_string(root.join("answers/2024/3.txt")).unwrap(),
+            "\n\n"
+        );
+        assert!(
+            std::fs::read_to_string(root.join("src/y2024/mod.rs"))
+                .unwrap()
+                .contains("    day1,\n    day3,\n    day10,\n")
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/main.rs")).unwrap(),
+            MAIN
+        );
+
+        std::fs::write(root.join("src/y2024/day3.rs"), "edited").unwrap();
+        let error = create(&root, 2024, &answers, &new_day).unwrap_err();
+        assert!(error.to_string().starts_with("Refusing to overwrite"));
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/y2024/day3.rs")).unwrap(),
+            "edited"
+        );
+
+        create(&root, 2026, &answers, &new_day).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/y2026/mod.rs")).unwrap(),
+            "use crate::registry;\n\nregistry::days! {\n    2026;\n    day3,\n}\n"
+        );
+        assert!(
+            std::fs::read_to_string(root.join("src/main.rs"))
+                .unwrap()
+                .contains("    y2024,\n    y2026,\n")
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
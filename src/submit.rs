@@ -0,0 +1,470 @@
+use std::fmt::{self, Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, anyhow, bail};
+use aoc_common::Part;
+use aoc_common::net::{self, HttpClient, Method, Request, Throttle, percent};
+use tracing::{error, info, warn};
+
+use crate::cache::THROTTLE_FILE;
+use crate::input::Inputs;
+use crate::registry::Entry;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submission {
+    pub day: u32,
+    pub part: Part,
+    /// The answer to submit, `None` runs the solver to find it.
+    pub answer: Option<String>,
+}
+
+/// What the site made of a submitted answer.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after a wrong answer, with the remaining wait if the site mentioned it.
+    RateLimited(Option<Duration>),
+    /// The part is either already solved or not unlocked yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Recognises the outcome from the page the site returns after a submission.
+    pub fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("too high") {
+                Ok(Outcome::TooHigh)
+            } else if page.contains("too low") {
+                Ok(Outcome::TooLow)
+            } else {
+                Ok(Outcome::Wrong)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Ok(Outcome::RateLimited(parse_wait(page)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Outcome::WrongLevel)
+        } else {
+            bail!("Unrecognised response to submission: {}", page.trim())
+        }
+    }
+
+    /// Whether the answer is known to be wrong after this outcome.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+/// Reads a wait like "You have 1m 5s left to wait".
+fn parse_wait(page: &str) -> Option<Duration> {
+    let (_, rest) = page.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    let mut seconds = 0;
+    for amount in wait.split_whitespace() {
+        seconds += match amount.split_at_checked(amount.len().checked_sub(1)?)? {
+            (minutes, "m") => minutes.parse::<u64>().ok()? * 60,
+            (secs, "s") => secs.parse().ok()?,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::RateLimited(_) => write!(f, "rate-limited"),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "wrong" => Ok(Outcome::Wrong),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "rate-limited" => Ok(Outcome::RateLimited(None)),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            _ => bail!("Unknown outcome: {s}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub part: Part,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+/// Every answer submitted for a day, stored as `<time> <part> <outcome> <answer>` lines.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn parse(contents: &str) -> Result<Self> {
+        let attempts = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let invalid = || anyhow!("Invalid history line: {line}");
+                let mut fields = line.splitn(4, ' ');
+                let mut field = || fields.next().ok_or_else(invalid);
+                Ok(Attempt {
+                    time: field()?.parse()?,
+                    part: match field()? {
+                        "a" => Part::A,
+                        "b" => Part::B,
+                        _ => return Err(invalid()),
+                    },
+                    outcome: field()?.parse()?,
+                    answer: field()?.to_owned(),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { attempts })
+    }
+
+    /// Fails if submitting `answer` for `part` is pointless: the part is already solved, the
+    /// answer was already rejected, or it lies outside the bounds set by too high and too low
+    /// answers.
+    pub fn check(&self, part: Part, answer: &str) -> Result<()> {
+        let attempts = self.attempts.iter().filter(|attempt| attempt.part == part);
+        let mut too_low: Option<i128> = None;
+        let mut too_high: Option<i128> = None;
+        for attempt in attempts {
+            if attempt.outcome == Outcome::Correct {
+                bail!("Part {part} was already solved with {}", attempt.answer);
+            }
+            if attempt.answer == answer && attempt.outcome.is_wrong() {
+                bail!(
+                    "{answer} was already submitted for part {part} and was {}",
+                    attempt.outcome
+                );
+            }
+            let Ok(value) = attempt.answer.parse() else {
+                continue;
+            };
+            match attempt.outcome {
+                Outcome::TooLow => too_low = too_low.max(Some(value)),
+                Outcome::TooHigh => too_high = Some(too_high.map_or(value, |high| high.min(value))),
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some(low) = too_low.filter(|&low| value <= low) {
+                bail!("{answer} is too low for part {part}, {low} was already too low");
+            }
+            if let Some(high) = too_high.filter(|&high| value >= high) {
+                bail!("{answer} is too high for part {part}, {high} was already too high");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Posts answers to the site, keeping the history of each day at `<dir>/<year>/<day>.history`
+/// next to the cached inputs.
+pub struct Submitter {
+    dir: PathBuf,
+    base_url: String,
+    client: Box<dyn HttpClient>,
+    session: Option<String>,
+    throttle: Throttle,
+}
+
+impl Submitter {
+    /// Shares the throttle of the input cache in `dir`, so fetching and submitting are spaced out
+    /// together.
+    pub fn new(
+        dir: PathBuf,
+        base_url: String,
+        client: Box<dyn HttpClient>,
+        throttle: Duration,
+    ) -> Self {
+        let throttle = Throttle::new(throttle, Some(dir.join(THROTTLE_FILE)));
+        Self {
+            dir,
+            base_url,
+            client,
+            session: None,
+            throttle,
+        }
+    }
+
+    /// Uses the given session token instead of looking it up with `net::load_session`.
+    #[cfg(test)]
+    pub fn with_session(mut self, session: String) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    pub fn history_path(&self, year: u32, day: u32) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("{day}.history"))
+    }
+
+    pub fn history(&self, year: u32, day: u32) -> Result<History> {
+        let path = self.history_path(year, day);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => History::parse(&contents)
+                .with_context(|| format!("Could not parse history at {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e).with_context(|| format!("Could not read {}", path.display())),
+        }
+    }
+
+    /// Submits `answer` unless the history shows it can't be right, and records the outcome.
+    pub fn submit(&self, year: u32, day: u32, part: Part, answer: &str) -> Result<Outcome> {
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            bail!("Refusing to submit {answer:?}, answers can't be empty or contain whitespace");
+        }
+        self.history(year, day)?.check(part, answer)?;
+        info!("Submitting {answer} for day {day} part {part}");
+
+        let session = match &self.session {
+            Some(session) => session.clone(),
+            None => net::load_session()?,
+        };
+        let level = match part {
+            Part::A => "1",
+            Part::B => "2",
+        };
+        let request = Request {
+            method: Method::Post,
+            url: format!("{}/{year}/day/{day}/answer", self.base_url),
+            session,
+            body: Some(percent::encode_form([("level", level), ("answer", answer)])),
+        };
+        let response = self.throttle.run(|| self.client.send(&request))?;
+        if response.status != 200 {
+            bail!(
+                "Could not submit answer for day {day} of {year}: HTTP {} {}",
+                response.status,
+                response.body.trim()
+            );
+        }
+
+        let outcome = Outcome::parse(&response.body)?;
+        self.record(
+            year,
+            day,
+            &Attempt {
+                time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+                part,
+                outcome,
+                answer: answer.to_owned(),
+            },
+        )?;
+        Ok(outcome)
+    }
+
+    fn record(&self, year: u32, day: u32, attempt: &Attempt) -> Result<()> {
+        let path = self.history_path(year, day);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Could not open {}", path.display()))?;
+        writeln!(
+            file,
+            "{} {} {} {}",
+            attempt.time, attempt.part, attempt.outcome, attempt.answer
+        )?;
+        Ok(())
+    }
+}
+
+/// Submits the answer, solving the day with `entry` first if no answer was given. Returns whether
+/// the answer was correct.
+pub fn run(
+    year: u32,
+    submission: &Submission,
+    entry: Option<&Entry>,
+    inputs: &Inputs,
+    submitter: &Submitter,
+) -> bool {
+    let Submission { day, part, .. } = *submission;
+    let answer = match (&submission.answer, entry) {
+        (Some(answer), _) => Ok(answer.clone()),
+        (None, Some(entry)) => inputs.load(year, day).and_then(|input| {
+            let run = entry.run(&input, part.into())?;
+            run.solutions
+                .get(part)
+                .map(str::to_owned)
+                .ok_or_else(|| anyhow!("Day {day} didn't produce an answer for part {part}"))
+        }),
+        (None, None) => Err(anyhow!(
+            "Day {day} is not implemented yet, pass the answer to submit"
+        )),
+    };
+    let outcome = answer.and_then(|answer| {
+        let outcome = submitter.submit(year, day, part, &answer)?;
+        Ok((answer, outcome))
+    });
+
+    match outcome {
+        Ok((answer, Outcome::Correct)) => {
+            info!("Day {day} part {part}: {answer} is correct");
+            return true;
+        }
+        Ok((answer, Outcome::Wrong)) => warn!("Day {day} part {part}: {answer} is wrong"),
+        Ok((answer, Outcome::TooHigh)) => warn!("Day {day} part {part}: {answer} is too high"),
+        Ok((answer, Outcome::TooLow)) => warn!("Day {day} part {part}: {answer} is too low"),
+        Ok((_, Outcome::RateLimited(Some(wait)))) => warn!(
+            "Day {day} part {part}: answered too recently, wait {wait:#?} before trying again"
+        ),
+        Ok((_, Outcome::RateLimited(None))) => {
+            warn!("Day {day} part {part}: answered too recently, wait before trying again");
+        }
+        Ok((_, Outcome::WrongLevel)) => {
+            warn!("Day {day} part {part}: already solved or not unlocked yet");
+        }
+        Err(e) => error!("{e:#}"),
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_common::Part;
+    use aoc_common::net::PlainHttpClient;
+
+    use super::{History, Outcome, Submitter};
+    use crate::harness;
+
+    #[test]
+    fn parses_outcomes() {
+        let page = |text: &str| format!("<main><article><p>{text}</p></article></main>");
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's the right answer! You are one gold star closer."
+            ))
+            .unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+            .unwrap(),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer. Please wait one minute."
+            ))
+            .unwrap(),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You gave an answer too recently. You have 1m 5s left to wait."
+            ))
+            .unwrap(),
+            Outcome::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::parse(&page("You don't seem to be solving the right level.")).unwrap(),
+            Outcome::WrongLevel
+        );
+        assert!(Outcome::parse("<html></html>").is_err());
+    }
+
+    #[test]
+    fn bounds() {
+        let history =
+            History::parse("1 a too-low 10\n2 a too-high 20\n3 a too-high 25\n4 b correct 7\n")
+                .unwrap();
+        assert!(history.check(Part::A, "15").is_ok());
+        assert!(history.check(Part::A, "abc").is_ok());
+        assert_eq!(
+            history.check(Part::A, "10").unwrap_err().to_string(),
+            "10 was already submitted for part a and was too-low"
+        );
+        assert_eq!(
+            history.check(Part::A, "5").unwrap_err().to_string(),
+            "5 is too low for part a, 10 was already too low"
+        );
+        assert_eq!(
+            history.check(Part::A, "22").unwrap_err().to_string(),
+            "22 is too high for part a, 20 was already too high"
+        );
+        assert_eq!(
+            history.check(Part::B, "8").unwrap_err().to_string(),
+            "Part b was already solved with 7"
+        );
+    }
+
+    #[test]
+    fn submits_and_records() {
+        let (server, dir, submitter) = harness::stub_site(
+            "submits",
+            |_| {
+                (
+                    200,
+                    "<article><p>That's not the right answer; your answer is too high.</p></article>"
+                        .to_owned(),
+                )
+            },
+            Submitter::new,
+            PlainHttpClient::default(),
+            Duration::ZERO,
+        );
+        let submitter = submitter.with_session("secret".to_owned());
+
+        assert_eq!(
+            submitter.submit(2025, 3, Part::B, "123").unwrap(),
+            Outcome::TooHigh
+        );
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2025/day/3/answer");
+        assert_eq!(requests[0].body, "level=2&answer=123");
+
+        let history = submitter.history(2025, 3).unwrap();
+        assert_eq!(history.attempts.len(), 1);
+        assert_eq!(history.attempts[0].part, Part::B);
+        assert_eq!(history.attempts[0].outcome, Outcome::TooHigh);
+        assert_eq!(history.attempts[0].answer, "123");
+
+        // Neither of these should reach the server
+        assert!(submitter.submit(2025, 3, Part::B, "123").is_err());
+        assert!(submitter.submit(2025, 3, Part::B, "150").is_err());
+        assert_eq!(server.requests().len(), 1);
+
+        assert_eq!(
+            submitter.submit(2025, 3, Part::B, "100").unwrap(),
+            Outcome::TooHigh
+        );
+        assert_eq!(server.requests().len(), 2);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+use aoc_common::{Day, Lines, ParseError, parse};
+use crate::registry::Entry;
+use anyhow::Result;
+
+pub const DAY: Entry = Entry::new(2025, 1, "Secret Entrance", &Day::CombinedLines(main));
+
+pub fn main(lines: Lines) -> Result<(i64, i64)> {
+    let mut solution_a = 0;
+    let mut solution_b = 0;
+
+    let mut num = 50;
+
+    for (i, line) in lines.enumerate() {
+        let line = line.trim();
+
+        let mut chars = line.chars();
+        let direction = match chars.next() {
+            Some('L') => -1,
+            Some('R') => 1,
+            Some(c) => return Err(ParseError::unexpected_char(c).at(i + 1, 1).into()),
+            None => return Err(ParseError::missing("a direction", line).at(i + 1, 1).into()),
+        };
+
+        let rest = chars.as_str();
+        let amount: i64 = parse::number(rest).map_err(|e| e.in_line(i + 1, line, rest))?;
+
+        for _ in 0..amount {
+            num = (num + direction) % 100;
+            if num == 0 {
+                solution_b += 1;
+            }
+        }
+
+        if num == 0 {
+            solution_a += 1;
+        }
+    }
+
+    Ok((solution_a, solution_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
+
+    #[test]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 3, 6);
+    }
+
+    #[test]
+    fn bad_input() {
+        harness::check_error(
+            &super::DAY,
+            "L68\nX30\n",
+            "Unexpected char 'X' at line 2, column 1",
+        );
+        harness::check_error(
+            &super::DAY,
+            "L68\n\nR4\n",
+            "Missing a direction in \"\" at line 2, column 1",
+        );
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
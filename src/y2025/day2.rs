@@ -0,0 +1,90 @@
+use aoc_common::{Day, ParseError, parse};
+use crate::registry::Entry;
+use anyhow::Result;
+
+pub const DAY: Entry = Entry::new(2025, 2, "Gift Shop", &Day::Combined(main));
+
+pub fn main(input: &str) -> Result<(u64, u64)> {
+    let mut lines = input.lines();
+
+    let mut solution_a = 0;
+    let mut solution_b = 0;
+
+    let Some(line) = lines.next() else {
+        return Err(ParseError::missing("the ranges", input).at(1, 1).into());
+    };
+    let line = line.trim();
+
+    for range in line.split(',') {
+        let (start, end) = range
+            .split_once('-')
+            .ok_or_else(|| ParseError::missing("'-'", range).locate(input, range))?;
+        let start: u64 = parse::number(start).map_err(|e| e.locate(input, start))?;
+        let end: u64 = parse::number(end).map_err(|e| e.locate(input, end))?;
+
+        for i in start..=end {
+            // 0 has one digit as well
+            let digits = i.checked_ilog10().map_or(1, |log| log + 1);
+            let factor = 10u64.pow(digits / 2);
+            if i / factor == i % factor {
+                solution_a += i;
+            }
+
+            'length_loop: for l in 1..=digits / 2 {
+                if digits % l != 0 {
+                    continue;
+                }
+
+                let factor = 10u64.pow(l);
+                let num = i % factor;
+                let mut mut_i = i;
+
+                while mut_i != 0 {
+                    if mut_i % factor != num {
+                        continue 'length_loop;
+                    }
+                    mut_i /= factor;
+                }
+
+                solution_b += i;
+                break;
+            }
+        }
+    }
+
+    Ok((solution_a, solution_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = "\
+11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124
+";
+
+    #[test]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 1_227_775_554_u64, 4_174_379_265_u64);
+    }
+
+    #[test]
+    fn bad_input() {
+        harness::check_error(
+            &super::DAY,
+            "",
+            "Missing the ranges in \"\" at line 1, column 1",
+        );
+        harness::check_error(
+            &super::DAY,
+            "11-22,95_115",
+            "Missing '-' in \"95_115\" at line 1, column 7",
+        );
+        harness::check_answers(&super::DAY, "0-11", 11, 11);
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
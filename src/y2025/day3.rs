@@ -0,0 +1,93 @@
+use aoc_common::{Day, Lines, ParseError};
+use crate::registry::Entry;
+use anyhow::Result;
+
+pub const DAY: Entry = Entry::new(2025, 3, "Lobby", &Day::CombinedLines(main));
+
+pub fn main(lines: Lines) -> Result<(u64, u64)> {
+    let mut solution_a = 0;
+    let mut solution_b = 0;
+
+    for (i, line) in lines.enumerate() {
+        let line = line.trim();
+        let chars = line
+            .chars()
+            .enumerate()
+            .map(|(x, c)| {
+                c.to_digit(10)
+                    .map(u64::from)
+                    .ok_or_else(|| ParseError::unexpected_char(c).at(i + 1, x + 1))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if chars.len() < 12 {
+            return Err(ParseError::missing("twelve batteries", line).at(i + 1, 1).into());
+        }
+
+        let (max_pos, first_digit) = chars[..chars.len() - 1]
+            .iter()
+            .enumerate()
+            .max_by(|x, y| Ord::cmp(&x.1, &y.1))
+            .unwrap();
+        let add = first_digit * 10 + chars[max_pos + 1..].iter().max().unwrap();
+        solution_a += add;
+
+        let (mut pos, mut num) = chars[..chars.len() - 11]
+            .iter()
+            .copied()
+            .enumerate()
+            .rev()
+            .max_by(|x, y| Ord::cmp(&x.1, &y.1))
+            .unwrap();
+        for digit in (0..11).rev() {
+            num *= 10;
+            let (offset, add) = chars[pos + 1..chars.len() - digit]
+                .iter()
+                .enumerate()
+                .rev()
+                .max_by(|x, y| Ord::cmp(&x.1, &y.1))
+                .unwrap();
+            pos += offset + 1;
+            num += add;
+        }
+
+        solution_b += num;
+    }
+
+    Ok((solution_a, solution_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+    #[test]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 357, 3_121_910_778_619_u64);
+    }
+
+    #[test]
+    fn bad_input() {
+        harness::check_error(
+            &super::DAY,
+            "987654321111111\n12345\n",
+            "Missing twelve batteries in \"12345\" at line 2, column 1",
+        );
+        harness::check_error(
+            &super::DAY,
+            "987654321111111\n\n",
+            "Missing twelve batteries in \"\" at line 2, column 1",
+        );
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
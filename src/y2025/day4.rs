@@ -0,0 +1,77 @@
+use aoc_common::automaton::{Automaton, CountRule};
+use aoc_common::{BitGrid, CharConvertable, Day, ParseError, phase};
+use crate::registry::Entry;
+use anyhow::Result;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Data {
+    ToiletRoll,
+}
+
+impl CharConvertable for Data {
+    fn to_char(option: Option<&Self>) -> char {
+        match option {
+            None => '.',
+            Some(Data::ToiletRoll) => '@',
+        }
+    }
+
+    fn from_char(c: char) -> Result<Option<Self>, ParseError> {
+        Ok(match c {
+            '.' => None,
+            '@' => Some(Data::ToiletRoll),
+            _ => return Err(ParseError::unexpected_char(c)),
+        })
+    }
+}
+
+pub const DAY: Entry = Entry::new(2025, 4, "Printing Department", &Day::Combined(main));
+
+pub fn main(input: &str) -> Result<(usize, usize)> {
+    let lines = input.lines();
+    let mut solution_a = 0;
+    let mut solution_b = 0;
+
+    phase("Parse");
+    let mut rolls = BitGrid::read::<Data>(lines)?;
+
+    phase("Removal rounds");
+    // Rolls with fewer than four neighbours are removed, no roll is ever added.
+    let removal = Automaton::counting(CountRule::new(&[], &[4, 5, 6, 7, 8]), true).diagonal(true);
+    removal.run(&mut rolls, |generation| {
+        if generation.number == 1 {
+            solution_a = generation.changed;
+        }
+        solution_b += generation.changed;
+    });
+
+    Ok((solution_a, solution_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+    #[test]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 13, 43);
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
@@ -1,17 +1,19 @@
-use crate::common;
+use aoc_common::{Day, Lines, ParseError, parse, phase};
+use crate::registry::Entry;
 use anyhow::Result;
 use std::iter::once;
 use std::ops::RangeInclusive;
 
-pub fn main() -> Result<(i64, usize)> {
-    let lines = common::read_lines("inputs/5.txt")?;
+pub const DAY: Entry = Entry::new(2025, 5, "Cafeteria", &Day::CombinedLines(main));
+
+pub fn main(lines: Lines) -> Result<(i64, usize)> {
+    phase("Parse and part a");
     let mut solution_a = 0;
 
     let mut fresh_ranges = Vec::new();
     let mut reading_ranges = true;
 
-    for line in lines {
-        let line = line?;
+    for (i, line) in lines.enumerate() {
         let line = line.trim();
 
         if reading_ranges {
@@ -19,18 +21,21 @@ pub fn main() -> Result<(i64, usize)> {
                 reading_ranges = false;
                 continue;
             }
-            let (start, end) = line.split_once('-').unwrap();
-            let start = start.parse()?;
-            let end = end.parse()?;
+            let (start, end) = line
+                .split_once('-')
+                .ok_or_else(|| ParseError::missing("'-'", line).at(i + 1, 1))?;
+            let start = parse::number(start).map_err(|e| e.in_line(i + 1, line, start))?;
+            let end = parse::number(end).map_err(|e| e.in_line(i + 1, line, end))?;
 
             add_range(&mut fresh_ranges, start, end);
         } else {
-            let id: i64 = line.parse()?;
+            let id: i64 = parse::number(line).map_err(|e| e.in_line(i + 1, line, line))?;
             if fresh_ranges.iter().any(|range| range.contains(&id)) {
                 solution_a += 1;
             }
         }
     }
+    phase("Part b");
     let solution_b = fresh_ranges.into_iter().map(RangeInclusive::count).sum();
 
     Ok((solution_a, solution_b))
@@ -57,3 +62,32 @@ fn add_range(ranges: &mut Vec<RangeInclusive<i64>>, mut start: i64, mut end: i64
     }
     ranges.push(start..=end);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
+    #[test]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 3, 14);
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
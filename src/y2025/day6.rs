@@ -0,0 +1,145 @@
+use aoc_common::{Day, Lines, ParseError, parse, phase};
+use crate::registry::Entry;
+use anyhow::Result;
+use regex::Regex;
+
+pub const DAY: Entry = Entry::new(2025, 6, "Trash Compactor", &Day::CombinedLines(main));
+
+pub fn main(lines: Lines) -> Result<(i64, i64)> {
+    phase("Parse and part a");
+    let re_ws = Regex::new("\\s+")?;
+
+    let mut solution_a = 0;
+    let mut solution_b = 0;
+
+    let mut columns = vec![];
+    let mut grid: Vec<Vec<char>> = vec![];
+
+    for (y, line) in lines.enumerate() {
+        grid.push(line.chars().collect());
+
+        for (i, p) in re_ws.split(line.trim()).enumerate() {
+            if p.is_empty() {
+                let error = ParseError::missing("a number or an operator", line).at(y + 1, 1);
+                return Err(error.into());
+            } else if columns.len() <= i {
+                columns.push(vec![parse::number::<i64>(p).map_err(|e| e.in_line(y + 1, line, p))?]);
+            } else if p.starts_with(|c: char| c.is_ascii_digit()) {
+                columns[i].push(parse::number(p).map_err(|e| e.in_line(y + 1, line, p))?);
+            } else {
+                solution_a += match p {
+                    "+" => columns[i].iter().copied().reduce(|l, r| l + r),
+                    "*" => columns[i].iter().copied().reduce(|l, r| l * r),
+                    _ => return Err(ParseError::unexpected(p).in_line(y + 1, line, p).into()),
+                }
+                .unwrap();
+            }
+        }
+    }
+
+    phase("Part b");
+    let Some(op_line) = grid.last() else {
+        return Err(ParseError::missing("the operators", "").into());
+    };
+    let missing_op = || {
+        let op_line: String = op_line.iter().collect();
+        ParseError::missing("an operator", op_line).at(grid.len(), 1)
+    };
+    let mut last_pos = 0;
+    let mut last_op = None;
+
+    for (i, c) in op_line.iter().enumerate() {
+        match c {
+            '+' | '*' => {
+                if let Some(op) = last_op {
+                    solution_b += accumulate_vertical(&grid, last_pos, op, i)?;
+                } else if i != 0 {
+                    // The columns before the first operator have no operator
+                    return Err(missing_op().into());
+                }
+                last_pos = i;
+                last_op = Some(*c);
+            }
+            ' ' => {}
+            _ => return Err(ParseError::unexpected_char(*c).at(grid.len(), i + 1).into()),
+        }
+    }
+
+    let last_op = last_op.ok_or_else(missing_op)?;
+    solution_b += accumulate_vertical(
+        &grid,
+        last_pos,
+        last_op,
+        grid.iter().map(Vec::len).max().unwrap() + 1,
+    )?;
+
+    Ok((solution_a, solution_b))
+}
+
+/// Folds the vertical numbers of the columns from `last_pos` up to `i` with `last_op`, which is
+/// either `+` or `*`.
+fn accumulate_vertical(
+    grid: &[Vec<char>],
+    last_pos: usize,
+    last_op: char,
+    i: usize,
+) -> Result<i64, ParseError> {
+    let mut acc = 0;
+    for x in last_pos..i - 1 {
+        let mut n = 0;
+        for (y, line) in grid.iter().take(grid.len() - 1).enumerate() {
+            match line.get(x) {
+                Some(' ') | None => {}
+                Some(c) => {
+                    let digit = c
+                        .to_digit(10)
+                        .ok_or_else(|| ParseError::unexpected_char(*c).at(y + 1, x + 1))?;
+                    n = n * 10 + i64::from(digit);
+                }
+            }
+        }
+
+        match last_op {
+            '*' if x == last_pos => acc = n,
+            '*' => acc *= n,
+            _ => acc += n,
+        }
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = concat!(
+        "123 328  51 64 \n",
+        " 45 64  387 23 \n",
+        "  6 98  215 314\n",
+        "*   +   *   +  \n",
+    );
+
+    #[test]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 4_277_556, 3_263_827);
+    }
+
+    #[test]
+    fn bad_input() {
+        harness::check_error(
+            &super::DAY,
+            "1 2\n\n* +\n",
+            "Missing a number or an operator in \"\" at line 2, column 1",
+        );
+        harness::check_error(
+            &super::DAY,
+            "1 2\n3 4\n * +\n",
+            "Missing an operator in \" * +\" at line 3, column 1",
+        );
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
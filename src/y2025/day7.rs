@@ -1,5 +1,5 @@
-use crate::common;
-use crate::common::{CharConvertable, InfiniteGrid, SignedCoordinate};
+use aoc_common::{CharConvertable, Day, Grid, ParseError, SignedCoordinate, phase};
+use crate::registry::Entry;
 use anyhow::Result;
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -18,25 +18,35 @@ impl CharConvertable for Data {
         }
     }
 
-    fn from_char(c: char) -> Option<Self> {
-        match c {
+    fn from_char(c: char) -> Result<Option<Self>, ParseError> {
+        Ok(match c {
             '.' => None,
             'S' => Some(Data::Start),
             '^' => Some(Data::Splitter),
-            _ => panic!("Unexpected char: {c}"),
-        }
+            _ => return Err(ParseError::unexpected_char(c)),
+        })
     }
 }
 
-pub fn main() -> Result<(i64, usize)> {
-    let lines = common::read_lines("inputs/7.txt")?;
+pub const DAY: Entry = Entry::new(2025, 7, "Laboratories", &Day::Combined(main));
+
+pub fn main(input: &str) -> Result<(i64, usize)> {
+    phase("Parse");
+    let lines = input.lines();
 
     let mut solution_a = 0;
 
-    let grid = InfiniteGrid::<SignedCoordinate, Data, false>::read(lines.map_while(Result::ok));
+    let grid = Grid::<Data>::read(lines)?;
+    if grid.width() == 0 || grid.height() == 0 {
+        return Err(ParseError::missing("the manifold", input).at(1, 1).into());
+    }
     let max = grid.extents().1;
 
-    let start = grid.entries().find(|(_, d)| *d == Data::Start).unwrap().0;
+    phase("Part a");
+    let Some((start, _)) = grid.entries().find(|(_, d)| **d == Data::Start) else {
+        let first_line = input.lines().next().unwrap_or_default();
+        return Err(ParseError::missing("the start 'S'", first_line).at(1, 1).into());
+    };
     let mut beams = FxHashSet::from_iter([start]);
     let mut new_beams = FxHashSet::default();
 
@@ -55,6 +65,7 @@ pub fn main() -> Result<(i64, usize)> {
         std::mem::swap(&mut beams, &mut new_beams);
     }
 
+    phase("Part b");
     let solution_b = count_universes(
         &grid,
         &mut vec![FxHashMap::default(); max.y as usize],
@@ -65,7 +76,7 @@ pub fn main() -> Result<(i64, usize)> {
 }
 
 fn count_universes(
-    grid: &InfiniteGrid<SignedCoordinate, Data, false>,
+    grid: &Grid<Data>,
     caches: &mut [FxHashMap<SignedCoordinate, usize>],
     beam: SignedCoordinate,
 ) -> usize {
@@ -90,3 +101,51 @@ fn count_universes(
         1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::harness;
+
+    const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
+    #[test]
+    fn example() {
+        harness::check_answers(&super::DAY, EXAMPLE, 21, 40);
+    }
+
+    #[test]
+    fn bad_input() {
+        harness::check_error(
+            &super::DAY,
+            "",
+            "Missing the manifold in \"\" at line 1, column 1",
+        );
+        harness::check_error(
+            &super::DAY,
+            "..\n.^\n",
+            "Missing the start 'S' in \"..\" at line 1, column 1",
+        );
+    }
+
+    #[test]
+    fn real_input() {
+        harness::check_real_input(&super::DAY);
+    }
+}
@@ -0,0 +1,12 @@
+use crate::registry;
+
+registry::days! {
+    2025;
+    day1,
+    day2,
+    day3,
+    day4,
+    day5,
+    day6,
+    day7,
+}